@@ -1,19 +1,23 @@
+use std::io::{self, Write};
+
 use anyhow::Result;
+use chrono::Utc;
 use ratatui::{
     Frame, Terminal,
-    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    crossterm::event::{Event, KeyCode, KeyEventKind},
     layout::{Position, Rect},
     prelude::Backend,
-    widgets::Widget,
 };
 
 use crate::{
+    common::index::CalendarIndex,
+    message::{AppMessage, MessageLoop},
     state::AppState,
     ui::{
         common::view::{FocusableView, View, ViewWithCursorControl},
         components::{
-            ics_popup::new_ics_popup, input_popup::InputPopup, layout::Layout,
-            popup_host::PopupHost, tmp_popup::new_tmp_popup, view_switcher::ViewSwitcher,
+            event_popup::new_event_popup, ics_popup::new_ics_popup, layout::Layout,
+            popup_host::PopupHost, reminder_banner::ReminderBanner, view_switcher::ViewSwitcher,
         },
         journal::Journal,
     },
@@ -27,61 +31,113 @@ enum AppStatus {
 pub struct App<'a> {
     status: AppStatus,
     state: AppState,
+    messages: MessageLoop,
 
     main: PopupHost<Layout<ViewSwitcher<'a>, Journal>>,
+    reminder_banner: ReminderBanner,
 }
 
 impl<'a> App<'a> {
+    /// `views` are switched between with `v`, the first one is shown at start
     pub fn new(
-        daily_view: &'a mut dyn FocusableView,
-        monthly_view: &'a mut dyn FocusableView,
+        views: Vec<&'a mut dyn FocusableView>,
         state: AppState,
+        messages: MessageLoop,
     ) -> Self {
-        let s = Self {
+        Self {
             status: AppStatus::Running,
+            messages,
+            reminder_banner: ReminderBanner::new(state.clone()),
             state: state.clone(),
             main: PopupHost::new(Layout::new(
-                ViewSwitcher::new('v').with_views(vec![daily_view, monthly_view]),
-                Journal::new(),
+                ViewSwitcher::new('v').with_views(views),
+                Journal::new(state.clone()),
             ))
             .with_popups(
                 vec![
                     Box::new(new_ics_popup(state.clone())),
-                    Box::new(new_tmp_popup(state.clone())),
+                    Box::new(new_event_popup(state)),
                 ],
-                vec![KeyCode::Char('i'), KeyCode::Char('j')],
+                vec![KeyCode::Char('i'), KeyCode::Enter],
             ),
-        };
-
-        s
+        }
     }
 
     pub fn run<B: Backend>(&mut self, term: &mut Terminal<B>) -> Result<()> {
+        self.check_reminders()?;
+
         loop {
-            match self.status {
-                AppStatus::Running => self.update(term)?,
-                AppStatus::Exiting => return Ok(()),
+            self.main.update();
+            term.draw(|frame| self.draw(frame))?;
+
+            // handle everything queued before drawing again
+            let mut message = Some(self.messages.next()?);
+            while let Some(m) = message {
+                self.handle_message(m)?;
+                message = self.messages.try_next();
+            }
+
+            if let AppStatus::Exiting = self.status {
+                return Ok(());
             }
         }
     }
 
-    fn update<B: Backend>(&mut self, term: &mut Terminal<B>) -> Result<()> {
-        self.main.update();
+    fn handle_message(&mut self, message: AppMessage) -> Result<()> {
+        match message {
+            AppMessage::Tick => self.check_reminders(),
+            AppMessage::Input(e) => self.handle_event(e),
+            AppMessage::InputFailed(e) => Err(e.context("failed to read terminal input")),
+            AppMessage::CalendarLoaded { path, result } => {
+                let mut state = self.state.state.borrow_mut();
+
+                // an older load finishing late
+                if state.loading.as_ref() != Some(&path) {
+                    return Ok(());
+                }
+                state.loading = None;
+
+                match result {
+                    Ok(calendar) => {
+                        let index = CalendarIndex::new(calendar, state.config.timezone);
+                        state.set_calendar(index);
+                        state.load_error = None;
+                    }
+                    Err(e) => state.load_error = Some(format!("{e:#}")),
+                }
+
+                Ok(())
+            }
+        }
+    }
 
-        term.draw(|frame| self.draw(frame))?;
+    fn check_reminders(&mut self) -> Result<()> {
+        let mut state = self.state.state.borrow_mut();
+        let state = &mut *state;
 
-        self.handle_events()?;
+        if state.reminders.check(state.calendar.as_ref(), Utc::now()) {
+            // terminal bell
+            let mut out = io::stdout();
+            out.write_all(b"\x07")?;
+            out.flush()?;
+        }
 
         Ok(())
     }
 
-    fn handle_events(&mut self) -> Result<()> {
-        let e = event::read()?;
+    fn handle_event(&mut self, e: Event) -> Result<()> {
         match e {
-            Event::Key(key_ev) if key_ev.kind == KeyEventKind::Press => match key_ev.code {
-                KeyCode::Char('q') => self.exit(),
-                _ => {}
-            },
+            Event::Key(key_ev)
+                if key_ev.kind == KeyEventKind::Press && !self.main.captures_input() =>
+            {
+                if self.reminder_banner.handle_key(key_ev.code) {
+                    return Ok(());
+                }
+
+                if let KeyCode::Char('q') = key_ev.code {
+                    self.exit()
+                }
+            }
 
             _ => {}
         }
@@ -96,8 +152,16 @@ impl<'a> App<'a> {
     fn draw(&self, frame: &mut Frame) {
         let mut cursor_pos = None;
 
+        let mut area = frame.area();
+        if self.reminder_banner.is_visible() && area.height > 1 {
+            self.reminder_banner
+                .render(Rect { height: 1, ..area }, frame.buffer_mut());
+            area.y += 1;
+            area.height -= 1;
+        }
+
         self.main
-            .render_with_cursor(frame.area(), frame.buffer_mut(), &mut |x, y| {
+            .render_with_cursor(area, frame.buffer_mut(), &mut |x, y| {
                 cursor_pos = Some(Position::new(x, y))
             });
 
@@ -1,28 +1,51 @@
-use std::{cell::RefCell, rc::Rc};
-
 use anyhow::Result;
+use ratatui::crossterm::{
+    event::{DisableBracketedPaste, EnableBracketedPaste},
+    execute,
+};
 use taskrs::{
     app::App,
+    config::Config,
+    message::MessageLoop,
     state::{AppState, State},
-    ui::{daily::DailyView, monthly::MonthlyView},
+    ui::{
+        agenda::AgendaView, daily::DailyView, monthly::MonthlyView, tasks::TaskListView,
+        weekly::WeeklyView, yearly::YearlyView,
+    },
 };
 
 fn main() -> Result<()> {
+    let config = Config::load()?;
+
     let mut term = ratatui::init();
+    // pasted text comes as one event instead of key presses
+    execute!(std::io::stdout(), EnableBracketedPaste)?;
 
-    let state = AppState {
-        state: Rc::new(RefCell::new(State {
-            calendar: None,
-            tmp: None,
-        })),
-    };
+    let messages = MessageLoop::new(config.tick_rate);
+    let state = AppState::new(State::new(config), messages.sender());
 
-    let mut daily_view = DailyView::new();
-    let mut monthly_view = MonthlyView::new();
-    let mut app = App::new(&mut monthly_view, &mut daily_view, state);
+    let mut daily_view = DailyView::new(state.clone());
+    let mut agenda_view = AgendaView::new(state.clone());
+    let mut monthly_view = MonthlyView::new(state.clone());
+    let mut weekly_view = WeeklyView::new(state.clone());
+    let mut tasks_view = TaskListView::new(state.clone());
+    let mut yearly_view = YearlyView::new(state.clone());
+    let mut app = App::new(
+        vec![
+            &mut monthly_view,
+            &mut weekly_view,
+            &mut daily_view,
+            &mut agenda_view,
+            &mut tasks_view,
+            &mut yearly_view,
+        ],
+        state,
+        messages,
+    );
 
     let res = app.run(&mut term);
 
+    let _ = execute!(std::io::stdout(), DisableBracketedPaste);
     ratatui::restore();
 
     res
@@ -0,0 +1,97 @@
+// Event alarms (VALARM), RFC 5545 3.6.6
+
+use std::{convert::Infallible, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::common::{
+    calendar::{CalendarEvent, RawProperty},
+    time::EventDuration,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlarmAction {
+    Display,
+    Audio,
+    Email,
+    Other(String),
+}
+
+/// When the alarm goes off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmTrigger {
+    /// offset from the event start, or from its end with RELATED=END
+    Relative {
+        offset: EventDuration,
+        from_end: bool,
+    },
+    Absolute(DateTime<Utc>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alarm {
+    pub action: AlarmAction,
+    pub trigger: AlarmTrigger,
+    pub description: Option<String>,
+    /// additional repetitions after the first one, `interval` apart
+    pub repeat: u32,
+    pub interval: Option<EventDuration>,
+    /// other properties (ATTENDEE, ATTACH, ...), kept for writing
+    pub extra: Vec<RawProperty>,
+}
+
+impl Alarm {
+    /// Instants the alarm goes off for given event instance, in order
+    pub fn fire_times(&self, event: &CalendarEvent, local: &Tz) -> Vec<DateTime<Utc>> {
+        let first = match self.trigger {
+            AlarmTrigger::Relative {
+                offset,
+                from_end: false,
+            } => event.start_utc(local) + offset.to_duration(),
+            AlarmTrigger::Relative {
+                offset,
+                from_end: true,
+            } => event.end_utc(local) + offset.to_duration(),
+            AlarmTrigger::Absolute(at) => at,
+        };
+
+        let interval = self.interval.map(|i| i.to_duration()).unwrap_or_default();
+        let repeat = if interval > chrono::Duration::zero() {
+            self.repeat
+        } else {
+            0
+        };
+
+        (0..=repeat).map(|i| first + interval * i as i32).collect()
+    }
+
+    /// Alarms that should be shown to the user (not e-mail ones)
+    pub fn is_reminder(&self) -> bool {
+        matches!(self.action, AlarmAction::Display | AlarmAction::Audio)
+    }
+}
+
+impl AlarmAction {
+    pub fn as_ical(&self) -> &str {
+        match self {
+            AlarmAction::Display => "DISPLAY",
+            AlarmAction::Audio => "AUDIO",
+            AlarmAction::Email => "EMAIL",
+            AlarmAction::Other(s) => s,
+        }
+    }
+}
+
+impl FromStr for AlarmAction {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "DISPLAY" => AlarmAction::Display,
+            "AUDIO" => AlarmAction::Audio,
+            "EMAIL" => AlarmAction::Email,
+            _ => AlarmAction::Other(s.to_string()),
+        })
+    }
+}
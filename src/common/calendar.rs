@@ -1,17 +1,106 @@
-use std::io::BufRead;
+use std::{collections::HashMap, convert::Infallible, io::BufRead, str::FromStr, sync::Arc};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
 
+use crate::common::{
+    alarm::Alarm,
+    journal::JournalEntry,
+    recurrence::Recurrence,
+    task::Task,
+    time::{EventDuration, EventTime, VTimezone},
+};
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct CalendarEvent {
     pub uid: String,
     pub summary: Option<String>,
     pub description: Option<String>,
     pub location: Option<String>,
-    pub start: DateTime<Utc>,
-    pub end: DateTime<Utc>,
+    pub start: EventTime,
+    /// always set, computed from `duration` or DTSTART when the event has no DTEND
+    pub end: EventTime,
+    /// DURATION as written, kept to write it back the same way
+    pub duration: Option<EventDuration>,
     pub status: EventStatus,
-    // pub recurrence_rule: Option<String>,
-    // pub attendees: Vec<String>, todo
+    pub recurrence: Option<Recurrence>,
+    /// original start of the instance this event overrides (RECURRENCE-ID)
+    pub recurrence_id: Option<EventTime>,
+    /// modified instances of a recurring event
+    pub overrides: Vec<CalendarEvent>,
+    /// DTSTAMP as read, written back; the time of writing is used when missing
+    pub dtstamp: Option<DateTime<Utc>>,
+    pub organizer: Option<Organizer>,
+    pub attendees: Vec<Attendee>,
+    /// properties not understood yet, written back as read
+    pub extra: Vec<RawProperty>,
+    pub alarms: Vec<Alarm>,
+    /// sub-components not understood yet (e.g. invalid VALARM), written back as read
+    pub components: Vec<RawComponent>,
+}
+
+/// Property kept verbatim, value is not unescaped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawProperty {
+    pub name: String,
+    pub params: Vec<(String, Vec<String>)>,
+    pub value: String,
+}
+
+/// Component kept verbatim, e.g. VALARM or VFREEBUSY
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawComponent {
+    pub name: String,
+    pub properties: Vec<RawProperty>,
+    pub components: Vec<RawComponent>,
+}
+
+/// ORGANIZER of an event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Organizer {
+    /// calendar user address, usually `mailto:...`
+    pub address: String,
+    /// CN parameter
+    pub name: Option<String>,
+    /// other parameters (SENT-BY, DIR, ...), kept for writing
+    pub params: Vec<(String, Vec<String>)>,
+}
+
+/// ATTENDEE of an event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attendee {
+    /// calendar user address, usually `mailto:...`
+    pub address: String,
+    /// CN parameter
+    pub name: Option<String>,
+    pub role: Role,
+    pub partstat: PartStat,
+    pub rsvp: bool,
+    /// other parameters (CUTYPE, DELEGATED-TO, ...), kept for writing
+    pub params: Vec<(String, Vec<String>)>,
+}
+
+/// ROLE parameter, REQ-PARTICIPANT when missing
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Role {
+    Chair,
+    #[default]
+    Required,
+    Optional,
+    NonParticipant,
+    Other(String),
+}
+
+/// PARTSTAT parameter, NEEDS-ACTION when missing
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PartStat {
+    #[default]
+    NeedsAction,
+    Accepted,
+    Declined,
+    Tentative,
+    Delegated,
+    Other(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,9 +111,332 @@ pub enum EventStatus {
     Unknown,
 }
 
+impl CalendarEvent {
+    /// All-day (or several whole days) event
+    pub fn is_all_day(&self) -> bool {
+        self.start.is_date()
+    }
+
+    /// Attendee entry of the user with given email
+    pub fn attendee(&self, email: &str) -> Option<&Attendee> {
+        self.attendees.iter().find(|a| a.is(email))
+    }
+
+    /// Whether the user with given email takes part in the event: they organize it,
+    /// accepted the invitation, or the event has no attendees at all (a personal event)
+    pub fn is_accepted_by(&self, email: &str) -> bool {
+        self.attendees.is_empty()
+            || self.organizer.as_ref().is_some_and(|o| o.is(email))
+            || self
+                .attendee(email)
+                .is_some_and(|a| a.partstat == PartStat::Accepted)
+    }
+
+    /// Web links of the event: the URL property first, then links found in the location,
+    /// description and other properties (e.g. conference links), without repeats
+    pub fn urls(&self) -> Vec<String> {
+        let (url, other): (Vec<&RawProperty>, Vec<&RawProperty>) =
+            self.extra.iter().partition(|p| p.name == "URL");
+
+        let texts = url
+            .iter()
+            .map(|p| p.value.as_str())
+            .chain(self.location.as_deref())
+            .chain(self.description.as_deref())
+            .chain(other.iter().map(|p| p.value.as_str()));
+
+        let mut urls: Vec<String> = vec![];
+        for url in texts.flat_map(find_urls) {
+            if !urls.iter().any(|u| u == url) {
+                urls.push(url.to_string());
+            }
+        }
+
+        urls
+    }
+
+    /// Days touched by the event in `local` zone, end inclusive
+    pub fn days(&self, local: &Tz) -> (NaiveDate, NaiveDate) {
+        let first = self.start.in_zone(local).date_naive();
+        // end is exclusive
+        let last = (self.end_utc(local) - Duration::seconds(1))
+            .with_timezone(local)
+            .date_naive();
+
+        (first, last.max(first))
+    }
+
+    /// Start instant, floating times are taken in `local` zone
+    pub fn start_utc(&self, local: &Tz) -> DateTime<Utc> {
+        self.start.utc(local)
+    }
+
+    /// End instant, floating times are taken in `local` zone
+    pub fn end_utc(&self, local: &Tz) -> DateTime<Utc> {
+        self.end.utc(local)
+    }
+
+    pub fn overlaps(&self, from: DateTime<Utc>, to: DateTime<Utc>, local: &Tz) -> bool {
+        let start = self.start_utc(local);
+        start < to && (self.end_utc(local) > from || start >= from)
+    }
+
+    /// Instances of this event overlapping `[from, to)`, recurring events are expanded
+    /// and overridden instances replaced by their overrides
+    pub fn occurrences_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        local: &Tz,
+    ) -> Vec<CalendarEvent> {
+        let Some(recurrence) = &self.recurrence else {
+            return if self.overlaps(from, to, local) {
+                vec![self.clone()]
+            } else {
+                vec![]
+            };
+        };
+
+        let overridden: Vec<DateTime<Utc>> = self
+            .overrides
+            .iter()
+            .filter_map(|o| o.recurrence_id.as_ref().map(|id| id.utc(local)))
+            .collect();
+
+        // instances starting a whole event length before `from` still overlap it, an hour
+        // more for the ones stretched by a DST change
+        let duration = self
+            .start
+            .duration_until(&self.end, local)
+            .max(Duration::zero());
+        let earliest = duration
+            .checked_add(&Duration::hours(1))
+            .and_then(|d| from.checked_sub_signed(d))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let mut occurrences: Vec<CalendarEvent> = recurrence
+            .occurrences_from(&self.start, earliest, local)
+            .take_while(|(start, _)| *start < to)
+            .filter(|(start, _)| !overridden.contains(start))
+            .map(|(_, start)| self.instance(start, local))
+            .filter(|e| e.overlaps(from, to, local))
+            .collect();
+
+        // overrides can move an instance in or out of the range
+        occurrences.extend(
+            self.overrides
+                .iter()
+                .filter(|o| o.overlaps(from, to, local))
+                .cloned(),
+        );
+        occurrences.sort_by_key(|e| e.start_utc(local));
+
+        occurrences
+    }
+
+    /// First instance starting after `after`, cancelled instances are skipped; so are all
+    /// instances of a cancelled series but its overrides, which have a status of their own
+    pub fn next_occurrence_after(&self, after: DateTime<Utc>, local: &Tz) -> Option<CalendarEvent> {
+        let Some(recurrence) = &self.recurrence else {
+            return (self.start_utc(local) > after && self.status != EventStatus::Cancelled)
+                .then(|| self.clone());
+        };
+
+        let overridden: Vec<DateTime<Utc>> = self
+            .overrides
+            .iter()
+            .filter_map(|o| o.recurrence_id.as_ref().map(|id| id.utc(local)))
+            .collect();
+
+        let from_rule = (self.status != EventStatus::Cancelled)
+            .then(|| {
+                recurrence
+                    .occurrences_from(&self.start, after, local)
+                    .skip_while(|(start, _)| *start <= after)
+                    .find(|(start, _)| !overridden.contains(start))
+            })
+            .flatten()
+            .map(|(_, start)| self.instance(start, local));
+
+        let from_overrides = self
+            .overrides
+            .iter()
+            .filter(|o| o.start_utc(local) > after && o.status != EventStatus::Cancelled)
+            .min_by_key(|o| o.start_utc(local))
+            .cloned();
+
+        from_rule
+            .into_iter()
+            .chain(from_overrides)
+            .min_by_key(|e| e.start_utc(local))
+    }
+
+    // instance of a recurring event starting at `start`, keeping the event length
+    fn instance(&self, start: EventTime, local: &Tz) -> CalendarEvent {
+        let duration = self.start.duration_until(&self.end, local);
+        let end = if self.start.same_zone(&self.end) {
+            start.with_naive(start.naive() + duration)
+        } else {
+            EventTime::Utc(start.utc(local) + duration)
+        };
+
+        CalendarEvent {
+            recurrence_id: Some(start.clone()),
+            start,
+            end,
+            overrides: vec![],
+            ..self.clone()
+        }
+    }
+}
+
+impl Organizer {
+    pub fn is(&self, email: &str) -> bool {
+        same_address(&self.address, email)
+    }
+}
+
+impl Attendee {
+    pub fn is(&self, email: &str) -> bool {
+        same_address(&self.address, email)
+    }
+}
+
+// compares `mailto:` address with plain email, case-insensitive
+fn same_address(address: &str, email: &str) -> bool {
+    let address = match address.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => &address[7..],
+        _ => address,
+    };
+
+    address.eq_ignore_ascii_case(email.trim())
+}
+
+impl Role {
+    pub fn as_ical(&self) -> &str {
+        match self {
+            Role::Chair => "CHAIR",
+            Role::Required => "REQ-PARTICIPANT",
+            Role::Optional => "OPT-PARTICIPANT",
+            Role::NonParticipant => "NON-PARTICIPANT",
+            Role::Other(s) => s,
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "CHAIR" => Role::Chair,
+            "REQ-PARTICIPANT" => Role::Required,
+            "OPT-PARTICIPANT" => Role::Optional,
+            "NON-PARTICIPANT" => Role::NonParticipant,
+            _ => Role::Other(s.to_string()),
+        })
+    }
+}
+
+impl PartStat {
+    pub fn as_ical(&self) -> &str {
+        match self {
+            PartStat::NeedsAction => "NEEDS-ACTION",
+            PartStat::Accepted => "ACCEPTED",
+            PartStat::Declined => "DECLINED",
+            PartStat::Tentative => "TENTATIVE",
+            PartStat::Delegated => "DELEGATED",
+            PartStat::Other(s) => s,
+        }
+    }
+}
+
+impl FromStr for PartStat {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "NEEDS-ACTION" => PartStat::NeedsAction,
+            "ACCEPTED" => PartStat::Accepted,
+            "DECLINED" => PartStat::Declined,
+            "TENTATIVE" => PartStat::Tentative,
+            "DELEGATED" => PartStat::Delegated,
+            _ => PartStat::Other(s.to_string()),
+        })
+    }
+}
+
+impl EventStatus {
+    /// STATUS value, `None` when the event had no (known) status
+    pub fn as_ical(&self) -> Option<&'static str> {
+        match self {
+            EventStatus::Confirmed => Some("CONFIRMED"),
+            EventStatus::Cancelled => Some("CANCELLED"),
+            EventStatus::Tentative => Some("TENTATIVE"),
+            EventStatus::Unknown => None,
+        }
+    }
+}
+
+impl FromStr for EventStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "CONFIRMED" => EventStatus::Confirmed,
+            "CANCELLED" => EventStatus::Cancelled,
+            "TENTATIVE" => EventStatus::Tentative,
+            _ => EventStatus::Unknown,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Calendar {
     pub name: String,
     pub events: Vec<CalendarEvent>,
+    pub tasks: Vec<Task>,
+    pub journals: Vec<JournalEntry>,
+    /// VTIMEZONE blocks of the file, including ones events don't use
+    pub timezones: Vec<Arc<VTimezone>>,
+    /// calendar properties not understood yet (e.g. METHOD)
+    pub extra: Vec<RawProperty>,
+    /// calendar components not understood yet (e.g. VFREEBUSY)
+    pub components: Vec<RawComponent>,
+}
+
+impl Calendar {
+    /// Build calendar from flat list of events, attaching RECURRENCE-ID
+    /// instances to their master event (same UID).
+    /// Overrides without a master are kept as standalone events.
+    pub fn from_events(name: String, events: Vec<CalendarEvent>) -> Self {
+        let (overrides, mut events): (Vec<_>, Vec<_>) =
+            events.into_iter().partition(|e| e.recurrence_id.is_some());
+
+        let masters: HashMap<String, usize> = events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.recurrence.is_some())
+            .map(|(i, e)| (e.uid.clone(), i))
+            .collect();
+
+        for o in overrides {
+            match masters.get(&o.uid) {
+                Some(&i) => events[i].overrides.push(o),
+                None => events.push(o),
+            }
+        }
+
+        Self {
+            name,
+            events,
+            tasks: vec![],
+            journals: vec![],
+            timezones: vec![],
+            extra: vec![],
+            components: vec![],
+        }
+    }
 }
 
 /// Trait for calendar data sources (Google Calendar, Apple Calendar, etc)
@@ -52,3 +464,16 @@ pub trait CalendarQuery {
     /// Return next upcoming event after given time
     fn next_event_after(&self, after: DateTime<Utc>) -> Option<CalendarEvent>;
 }
+
+// `http(s)://` links in free text, ending at whitespace, quotes or brackets; punctuation
+// closing a sentence is not part of them
+fn find_urls(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| c.is_whitespace() || "<>\"'()[]{}\\".contains(c))
+        .filter_map(|word| {
+            let start = word.find("https://").or_else(|| word.find("http://"))?;
+            let url = word[start..].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+            url.split_once("://")
+                .is_some_and(|(_, rest)| !rest.is_empty())
+                .then_some(url)
+        })
+}
@@ -0,0 +1,62 @@
+// Input history, one entry per line in `<data dir>/taskrs/history/<name>`
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+
+// older entries are dropped past this
+const MAX_ENTRIES: usize = 100;
+
+#[derive(Default)]
+pub struct History {
+    path: Option<PathBuf>,
+    // oldest first
+    entries: Vec<String>,
+}
+
+impl History {
+    /// History kept in memory only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads history with given name, empty if there is none yet
+    pub fn load(name: &str) -> Self {
+        let path = dirs::data_dir().map(|d| d.join("taskrs").join("history").join(name));
+        let entries = path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map(|s| s.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Self { path, entries }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Adds entry as the newest one and saves history, repeated entries are moved
+    pub fn push(&mut self, entry: &str) -> Result<()> {
+        // entries are lines
+        let entry = entry.replace(['\n', '\r'], " ");
+        if entry.trim().is_empty() {
+            return Ok(());
+        }
+
+        self.entries.retain(|e| *e != entry);
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, self.entries.join("\n") + "\n")?;
+        }
+
+        Ok(())
+    }
+}
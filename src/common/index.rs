@@ -0,0 +1,244 @@
+// Indexed event store answering `CalendarQuery` without scanning every event
+//
+// Every plain event and every recurring series is an interval `[start, end)`.
+// Intervals are sorted by start, and a prefix maximum of their ends tells where
+// the first interval that may still overlap a given instant is, so a range
+// query only looks at candidates between two binary searches.
+// Recurring series are stored once and expanded only for the queried range.
+// Series without an end are kept aside, one of them in the sorted list would
+// push the prefix maximum to the end of time and turn every query into a scan.
+// Views ask for the same few ranges on every render, the last expanded ones are
+// kept; the index is rebuilt whenever the calendar changes, and the cache with it.
+
+use std::{cell::RefCell, collections::VecDeque};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+use crate::common::{
+    calendar::{Calendar, CalendarEvent, CalendarQuery},
+    recurrence::Until,
+    time::EventTime,
+};
+
+// expanded ranges kept by an index
+const CACHED_RANGES: usize = 32;
+
+// result of `events_in_range` for `[start, end)`
+struct Expanded {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    events: Vec<CalendarEvent>,
+}
+
+struct Entry {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    event: usize,
+}
+
+pub struct CalendarIndex {
+    calendar: Calendar,
+    // zone floating times are resolved in
+    tz: Tz,
+
+    // bounded events and series, sorted by start
+    entries: Vec<Entry>,
+    // max_end[i] = max end of entries[..=i]
+    max_end: Vec<DateTime<Utc>>,
+    // series which never end, sorted by start
+    open: Vec<Entry>,
+
+    // most recently used last
+    expanded: RefCell<VecDeque<Expanded>>,
+}
+
+impl CalendarIndex {
+    pub fn new(calendar: Calendar, tz: Tz) -> Self {
+        let (mut entries, mut open): (Vec<Entry>, Vec<Entry>) = calendar
+            .events
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let (start, end) = span(e, &tz);
+                Entry {
+                    start,
+                    end,
+                    event: i,
+                }
+            })
+            .partition(|e| e.end < DateTime::<Utc>::MAX_UTC);
+        entries.sort_by_key(|e| e.start);
+        open.sort_by_key(|e| e.start);
+
+        let max_end = entries
+            .iter()
+            .scan(DateTime::<Utc>::MIN_UTC, |max, e| {
+                *max = (*max).max(e.end);
+                Some(*max)
+            })
+            .collect();
+
+        Self {
+            calendar,
+            tz,
+            entries,
+            max_end,
+            open,
+            expanded: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    pub fn tz(&self) -> &Tz {
+        &self.tz
+    }
+
+    /// Event as stored in the calendar for an event returned by a query: the override an
+    /// instance comes from, the series of other instances, the event itself otherwise
+    pub fn stored(&self, event: &CalendarEvent) -> Option<&CalendarEvent> {
+        let same = |a: &Option<EventTime>, b: &Option<EventTime>| {
+            a.as_ref().map(|t| t.utc(&self.tz)) == b.as_ref().map(|t| t.utc(&self.tz))
+        };
+        let mut events = self.calendar.events.iter().filter(|e| e.uid == event.uid);
+
+        // overrides without their series are stored on their own
+        events
+            .clone()
+            .find(|e| same(&e.recurrence_id, &event.recurrence_id))
+            .or_else(|| {
+                let series = events.find(|e| e.recurrence.is_some())?;
+                let instance = series
+                    .overrides
+                    .iter()
+                    .find(|o| same(&o.recurrence_id, &event.recurrence_id));
+
+                Some(instance.unwrap_or(series))
+            })
+    }
+
+    // entries which may overlap `[from, to)`
+    fn candidates(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> impl Iterator<Item = &Entry> {
+        let hi = self.entries.partition_point(|e| e.start < to);
+        let lo = self.max_end[..hi].partition_point(|end| *end < from);
+        let open = self.open.partition_point(|e| e.start < to);
+
+        self.entries[lo..hi].iter().chain(&self.open[..open])
+    }
+}
+
+impl CalendarQuery for CalendarIndex {
+    fn events_in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<CalendarEvent> {
+        let mut expanded = self.expanded.borrow_mut();
+        if let Some(i) = expanded
+            .iter()
+            .position(|e| e.start == start && e.end == end)
+        {
+            let hit = expanded.remove(i).unwrap();
+            let events = hit.events.clone();
+            expanded.push_back(hit);
+            return events;
+        }
+
+        let mut events: Vec<CalendarEvent> = self
+            .candidates(start, end)
+            .flat_map(|e| self.calendar.events[e.event].occurrences_between(start, end, &self.tz))
+            .collect();
+        events.sort_by_key(|e| e.start_utc(&self.tz));
+
+        if expanded.len() == CACHED_RANGES {
+            expanded.pop_front();
+        }
+        expanded.push_back(Expanded {
+            start,
+            end,
+            events: events.clone(),
+        });
+
+        events
+    }
+
+    /// Cancelled events and instances are skipped
+    fn next_event_after(&self, after: DateTime<Utc>) -> Option<CalendarEvent> {
+        let mut best: Option<CalendarEvent> = None;
+        let mut best_start = DateTime::<Utc>::MAX_UTC;
+
+        let first_later = self.entries.partition_point(|e| e.start <= after);
+        let lo = self.max_end.partition_point(|end| *end <= after);
+
+        // open series first, the earliest of them cuts the scan of the sorted list short
+        for entries in [&self.open[..], &self.entries[lo.min(first_later)..]] {
+            for entry in entries {
+                if entry.start >= best_start {
+                    break;
+                }
+
+                let next = self.calendar.events[entry.event].next_occurrence_after(after, &self.tz);
+
+                if let Some(next) = next
+                    && next.start_utc(&self.tz) < best_start
+                {
+                    best_start = next.start_utc(&self.tz);
+                    best = Some(next);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+// interval covered by an event, whole series for recurring events
+fn span(event: &CalendarEvent, tz: &Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+    let mut start = event.start_utc(tz);
+    let mut end = event.end_utc(tz).max(start);
+
+    if let Some(recurrence) = &event.recurrence {
+        let duration = end - start;
+
+        let last_start = match &recurrence.rule {
+            Some(rule) => match (rule.count, rule.until) {
+                // bounded series are short enough to walk once
+                (Some(_), _) => recurrence
+                    .occurrences(&event.start, tz)
+                    .last()
+                    .map(|(s, _)| s),
+                (_, Some(Until::Utc(until))) => Some(until),
+                (_, Some(Until::Floating(until))) => Some(event.start.with_naive(until).utc(tz)),
+                (_, Some(Until::Date(until))) => Some(midnight_after(until, &event.start, tz)),
+                (None, None) => None,
+            },
+            None => recurrence
+                .occurrences(&event.start, tz)
+                .last()
+                .map(|(s, _)| s),
+        };
+
+        let rdates_end = recurrence.rdates.iter().map(|d| d.utc(tz)).max();
+
+        end = match last_start {
+            // a series ending at the end of time never ends either
+            Some(last) => end.max(
+                last.max(rdates_end.unwrap_or(last))
+                    .checked_add_signed(duration)
+                    .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            ),
+            None => DateTime::<Utc>::MAX_UTC,
+        };
+    }
+
+    for o in &event.overrides {
+        start = start.min(o.start_utc(tz));
+        end = end.max(o.end_utc(tz));
+    }
+
+    (start, end)
+}
+
+fn midnight_after(date: NaiveDate, start: &EventTime, tz: &Tz) -> DateTime<Utc> {
+    let next = date.succ_opt().unwrap_or(date);
+    start.with_naive(next.and_time(NaiveTime::MIN)).utc(tz)
+}
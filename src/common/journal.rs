@@ -0,0 +1,30 @@
+// Journal entries (VJOURNAL), RFC 5545 3.6.3
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+use crate::common::{calendar::RawProperty, time::EventTime};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub uid: String,
+    /// DTSTART, a date for entries written by us
+    pub start: EventTime,
+    pub summary: Option<String>,
+    /// first DESCRIPTION, other ones are kept in `extra`
+    pub description: String,
+    /// DTSTAMP as read, written back; the time of writing is used when missing
+    pub dtstamp: Option<DateTime<Utc>>,
+    /// properties not understood yet, written back as read
+    pub extra: Vec<RawProperty>,
+}
+
+impl JournalEntry {
+    /// Day the entry belongs to in `local` zone
+    pub fn day(&self, local: &Tz) -> NaiveDate {
+        match &self.start {
+            EventTime::Date(d) => *d,
+            start => start.in_zone(local).date_naive(),
+        }
+    }
+}
@@ -1 +1,9 @@
+pub mod alarm;
 pub mod calendar;
+pub mod history;
+pub mod index;
+pub mod journal;
+pub mod recurrence;
+pub mod reminder;
+pub mod task;
+pub mod time;
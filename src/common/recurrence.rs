@@ -0,0 +1,1011 @@
+// RFC 5545 recurrence rules (RRULE) and recurrence sets (RRULE + RDATE - EXDATE)
+//
+// Expansion is done on wall clock (naive) datetimes, so the same engine works
+// for any timezone the event start is expressed in.
+
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+use anyhow::{Result, anyhow, bail};
+use chrono::{
+    DateTime, Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+
+use crate::common::time::EventTime;
+
+// stop expanding if rule produced nothing for that many periods in a row
+// (e.g. BYMONTH=2;BYMONTHDAY=30)
+const MAX_EMPTY_PERIODS: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Until {
+    Date(NaiveDate),
+    Floating(NaiveDateTime),
+    Utc(DateTime<Utc>),
+}
+
+/// BYDAY entry, e.g. `MO`, `2TU` or `-1SU`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekdayNum {
+    pub ord: Option<i32>,
+    pub weekday: Weekday,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<Until>,
+    pub by_second: Vec<u32>,
+    pub by_minute: Vec<u32>,
+    pub by_hour: Vec<u32>,
+    pub by_day: Vec<WeekdayNum>,
+    pub by_month_day: Vec<i32>,
+    pub by_year_day: Vec<i32>,
+    pub by_week_no: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+    pub wkst: Weekday,
+    /// `X-NAME=value` parts, not used for expansion but written back
+    pub x_parts: Vec<(String, String)>,
+}
+
+/// Full recurrence set of an event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recurrence {
+    pub rule: Option<RecurrenceRule>,
+    pub rdates: Vec<EventTime>,
+    pub exdates: Vec<EventTime>,
+}
+
+impl Recurrence {
+    pub fn is_empty(&self) -> bool {
+        self.rule.is_none() && self.rdates.is_empty()
+    }
+
+    /// All instance starts of an event starting at `dtstart`, in order.
+    /// Rule is expanded on the wall clock of `dtstart`, floating times use `local` zone.
+    pub fn occurrences<'a>(
+        &'a self,
+        dtstart: &'a EventTime,
+        local: &'a Tz,
+    ) -> impl Iterator<Item = (DateTime<Utc>, EventTime)> + 'a {
+        self.occurrences_from(dtstart, DateTime::<Utc>::MIN_UTC, local)
+    }
+
+    /// Instance starts from `from` on, in order. Rules without COUNT skip the periods
+    /// before `from` instead of expanding them.
+    pub fn occurrences_from<'a>(
+        &'a self,
+        dtstart: &'a EventTime,
+        from: DateTime<Utc>,
+        local: &'a Tz,
+    ) -> impl Iterator<Item = (DateTime<Utc>, EventTime)> + 'a {
+        let rule: Box<dyn Iterator<Item = EventTime>> = match &self.rule {
+            Some(rule) => {
+                let mut iter = rule.iter(dtstart.naive(), |utc| dtstart.wall_clock_of(utc, local));
+                if from > dtstart.utc(local) {
+                    iter.seek(dtstart.wall_clock_of(from, local));
+                }
+                Box::new(iter.map(|dt| dtstart.with_naive(dt)))
+            }
+            None => Box::new(std::iter::once(dtstart.clone())),
+        };
+
+        let mut rdates: Vec<(DateTime<Utc>, EventTime)> = self
+            .rdates
+            .iter()
+            .map(|dt| (dt.utc(local), dt.clone()))
+            .collect();
+        rdates.sort_by_key(|(utc, _)| *utc);
+
+        let exdates: Vec<DateTime<Utc>> = self.exdates.iter().map(|dt| dt.utc(local)).collect();
+
+        MergeSorted {
+            a: rule.map(|dt| (dt.utc(local), dt)).peekable(),
+            b: rdates.into_iter().peekable(),
+            last: None,
+        }
+        .skip_while(move |(utc, _)| *utc < from)
+        .filter(move |(utc, _)| !exdates.contains(utc))
+    }
+}
+
+impl RecurrenceRule {
+    pub fn new(freq: Frequency) -> Self {
+        Self {
+            freq,
+            interval: 1,
+            count: None,
+            until: None,
+            by_second: vec![],
+            by_minute: vec![],
+            by_hour: vec![],
+            by_day: vec![],
+            by_month_day: vec![],
+            by_year_day: vec![],
+            by_week_no: vec![],
+            by_month: vec![],
+            by_set_pos: vec![],
+            wkst: Weekday::Mon,
+            x_parts: vec![],
+        }
+    }
+
+    /// Expand the rule for wall clock `dtstart`.
+    /// `to_local` converts a UTC UNTIL into the same wall clock as `dtstart`.
+    pub fn iter(
+        &self,
+        dtstart: NaiveDateTime,
+        to_local: impl Fn(DateTime<Utc>) -> NaiveDateTime,
+    ) -> RuleIter<'_> {
+        let until = self.until.map(|u| match u {
+            Until::Date(d) => d.and_time(NaiveTime::MIN) + Duration::days(1) - Duration::seconds(1),
+            Until::Floating(dt) => dt,
+            Until::Utc(dt) => to_local(dt),
+        });
+
+        RuleIter {
+            rule: self,
+            dtstart,
+            until,
+            period: 0,
+            buf: VecDeque::new(),
+            emitted: 0,
+            empty_periods: 0,
+            done: false,
+        }
+    }
+
+    fn period_start(&self, dtstart: NaiveDateTime, period: u64) -> Option<NaiveDateTime> {
+        let step = period.checked_mul(self.interval as u64)?;
+        let date = dtstart.date();
+
+        match self.freq {
+            Frequency::Yearly => {
+                let year = dtstart.year().checked_add(i32::try_from(step).ok()?)?;
+                NaiveDate::from_ymd_opt(year, 1, 1).map(|d| d.and_time(dtstart.time()))
+            }
+            Frequency::Monthly => date
+                .with_day(1)?
+                .checked_add_months(Months::new(u32::try_from(step).ok()?))
+                .map(|d| d.and_time(dtstart.time())),
+            Frequency::Weekly => {
+                let week_start = date.week(self.wkst).first_day();
+                week_start
+                    .checked_add_days(Days::new(step.checked_mul(7)?))
+                    .map(|d| d.and_time(dtstart.time()))
+            }
+            Frequency::Daily => date
+                .checked_add_days(Days::new(step))
+                .map(|d| d.and_time(dtstart.time())),
+            Frequency::Hourly => {
+                dtstart.checked_add_signed(Duration::try_hours(i64::try_from(step).ok()?)?)
+            }
+            Frequency::Minutely => {
+                dtstart.checked_add_signed(Duration::try_minutes(i64::try_from(step).ok()?)?)
+            }
+            Frequency::Secondly => {
+                dtstart.checked_add_signed(Duration::try_seconds(i64::try_from(step).ok()?)?)
+            }
+        }
+    }
+
+    // whole periods from the one of `dtstart` to the one of `dt`
+    fn periods_until(&self, dtstart: NaiveDateTime, dt: NaiveDateTime) -> i64 {
+        let periods = match self.freq {
+            Frequency::Yearly => (dt.year() - dtstart.year()) as i64,
+            Frequency::Monthly => {
+                (dt.year() - dtstart.year()) as i64 * 12 + dt.month() as i64
+                    - dtstart.month() as i64
+            }
+            Frequency::Weekly => {
+                let week_start = |d: NaiveDateTime| d.date().week(self.wkst).first_day();
+                (week_start(dt) - week_start(dtstart)).num_days() / 7
+            }
+            Frequency::Daily => (dt.date() - dtstart.date()).num_days(),
+            Frequency::Hourly => (dt - dtstart).num_hours(),
+            Frequency::Minutely => (dt - dtstart).num_minutes(),
+            Frequency::Secondly => (dt - dtstart).num_seconds(),
+        };
+
+        periods / self.interval.max(1) as i64
+    }
+
+    // candidate days of the period starting at `start`
+    fn period_days(&self, start: NaiveDate) -> Vec<NaiveDate> {
+        // weeks can start in the year before or end in the year after
+        if self.freq == Frequency::Yearly && !self.by_week_no.is_empty() {
+            let mut days: Vec<NaiveDate> = self
+                .by_week_no
+                .iter()
+                .filter_map(|&n| self.week_start(start.year(), n))
+                .flat_map(|first| first.iter_days().take(7))
+                .collect();
+            days.sort();
+            days.dedup();
+            return days;
+        }
+
+        if self.freq == Frequency::Yearly && !self.by_month.is_empty() {
+            return self
+                .by_month
+                .iter()
+                .filter_map(|&m| NaiveDate::from_ymd_opt(start.year(), m, 1))
+                .flat_map(|first| {
+                    first
+                        .iter_days()
+                        .take_while(move |d| d.month() == first.month())
+                })
+                .collect();
+        }
+
+        let (first, len) = match self.freq {
+            Frequency::Yearly => (start, if start.leap_year() { 366 } else { 365 }),
+            Frequency::Monthly => (start, start.num_days_in_month() as u64),
+            Frequency::Weekly => (start, 7),
+            _ => (start, 1),
+        };
+
+        (0..len)
+            .filter_map(|i| first.checked_add_days(Days::new(i)))
+            .collect()
+    }
+
+    // first day of week `n` of `year`, negative counts from the end; week 1 is the first
+    // one starting on WKST with at least four days in the year
+    fn week_start(&self, year: i32, n: i32) -> Option<NaiveDate> {
+        let first_week = |year| {
+            let jan1 = NaiveDate::from_ymd_opt(year, 1, 1)?;
+            let offset =
+                (jan1.weekday().num_days_from_monday() + 7 - self.wkst.num_days_from_monday()) % 7;
+            if offset <= 3 {
+                jan1.checked_sub_days(Days::new(offset as u64))
+            } else {
+                jan1.checked_add_days(Days::new(7 - offset as u64))
+            }
+        };
+
+        let first = first_week(year)?;
+        let weeks = ((first_week(year + 1)? - first).num_days() / 7) as i32;
+        let n = if n < 0 { weeks + n + 1 } else { n };
+        if !(1..=weeks).contains(&n) {
+            return None;
+        }
+
+        first.checked_add_days(Days::new(7 * (n as u64 - 1)))
+    }
+
+    fn day_matches(&self, day: NaiveDate, dtstart: NaiveDateTime) -> bool {
+        if !self.by_month.is_empty() && !self.by_month.contains(&day.month()) {
+            return false;
+        }
+
+        if !self.by_year_day.is_empty() {
+            let len = if day.leap_year() { 366 } else { 365 };
+            let d = day.ordinal() as i32;
+            if !self
+                .by_year_day
+                .iter()
+                .any(|&yd| yd == d || (yd < 0 && len + yd + 1 == d))
+            {
+                return false;
+            }
+        }
+
+        if !self.by_month_day.is_empty() {
+            let len = day.num_days_in_month() as i32;
+            let d = day.day() as i32;
+            if !self
+                .by_month_day
+                .iter()
+                .any(|&md| md == d || (md < 0 && len + md + 1 == d))
+            {
+                return false;
+            }
+        }
+
+        if !self.by_day.is_empty() && !self.by_day.iter().any(|wd| self.weekday_matches(day, *wd)) {
+            return false;
+        }
+
+        // implicit parts taken from DTSTART (RFC 5545, 3.3.10)
+        let no_day_rules = self.by_day.is_empty()
+            && self.by_month_day.is_empty()
+            && self.by_year_day.is_empty()
+            && self.by_week_no.is_empty();
+        match self.freq {
+            Frequency::Yearly if no_day_rules => {
+                (!self.by_month.is_empty() || day.month() == dtstart.month())
+                    && day.day() == dtstart.day()
+            }
+            Frequency::Monthly if no_day_rules => day.day() == dtstart.day(),
+            Frequency::Weekly if self.by_day.is_empty() => day.weekday() == dtstart.weekday(),
+            _ => true,
+        }
+    }
+
+    fn weekday_matches(&self, day: NaiveDate, wd: WeekdayNum) -> bool {
+        if day.weekday() != wd.weekday {
+            return false;
+        }
+
+        let Some(ord) = wd.ord else {
+            return true;
+        };
+
+        // ordinals are relative to the month for MONTHLY (and YEARLY with BYMONTH),
+        // relative to the year for plain YEARLY and meaningless otherwise
+        let (idx, len) = match self.freq {
+            Frequency::Monthly => (day.day0() as i32, day.num_days_in_month() as i32),
+            Frequency::Yearly if !self.by_month.is_empty() => {
+                (day.day0() as i32, day.num_days_in_month() as i32)
+            }
+            Frequency::Yearly => (
+                day.ordinal0() as i32,
+                if day.leap_year() { 366 } else { 365 },
+            ),
+            _ => return true,
+        };
+
+        if ord > 0 {
+            idx / 7 + 1 == ord
+        } else {
+            -((len - 1 - idx) / 7 + 1) == ord
+        }
+    }
+
+    fn times(&self, period_start: NaiveDateTime) -> Vec<NaiveTime> {
+        let or = |v: &Vec<u32>, d: u32| if v.is_empty() { vec![d] } else { v.clone() };
+        let (hours, minutes, seconds) = match self.freq {
+            Frequency::Secondly => (
+                vec![period_start.hour()],
+                vec![period_start.minute()],
+                vec![period_start.second()],
+            ),
+            Frequency::Minutely => (
+                vec![period_start.hour()],
+                vec![period_start.minute()],
+                or(&self.by_second, period_start.second()),
+            ),
+            Frequency::Hourly => (
+                vec![period_start.hour()],
+                or(&self.by_minute, period_start.minute()),
+                or(&self.by_second, period_start.second()),
+            ),
+            _ => (
+                or(&self.by_hour, period_start.hour()),
+                or(&self.by_minute, period_start.minute()),
+                or(&self.by_second, period_start.second()),
+            ),
+        };
+
+        let mut times = vec![];
+        for &h in &hours {
+            for &m in &minutes {
+                for &s in &seconds {
+                    if let Some(t) = NaiveTime::from_hms_opt(h, m, s) {
+                        // BYxxx parts finer than FREQ limit instead of expanding
+                        let limited = (self.freq <= Frequency::Hourly
+                            && !self.by_hour.is_empty()
+                            && !self.by_hour.contains(&h))
+                            || (self.freq <= Frequency::Minutely
+                                && !self.by_minute.is_empty()
+                                && !self.by_minute.contains(&m))
+                            || (self.freq == Frequency::Secondly
+                                && !self.by_second.is_empty()
+                                && !self.by_second.contains(&s));
+                        if !limited {
+                            times.push(t);
+                        }
+                    }
+                }
+            }
+        }
+        times.sort();
+        times
+    }
+
+    fn expand_period(
+        &self,
+        dtstart: NaiveDateTime,
+        period_start: NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        let times = self.times(period_start);
+
+        let mut set: Vec<NaiveDateTime> = self
+            .period_days(period_start.date())
+            .into_iter()
+            .filter(|d| self.day_matches(*d, dtstart))
+            .flat_map(|d| times.iter().map(move |t| d.and_time(*t)))
+            .collect();
+        set.sort();
+
+        if !self.by_set_pos.is_empty() {
+            let len = set.len() as i32;
+            let mut picked: Vec<NaiveDateTime> = self
+                .by_set_pos
+                .iter()
+                .filter_map(|&pos| {
+                    let idx = if pos > 0 { pos - 1 } else { len + pos };
+                    (0..len).contains(&idx).then(|| set[idx as usize])
+                })
+                .collect();
+            picked.sort();
+            picked.dedup();
+            set = picked;
+        }
+
+        set
+    }
+}
+
+pub struct RuleIter<'a> {
+    rule: &'a RecurrenceRule,
+    dtstart: NaiveDateTime,
+    until: Option<NaiveDateTime>,
+
+    period: u64,
+    buf: VecDeque<NaiveDateTime>,
+
+    emitted: u32,
+    empty_periods: u32,
+    done: bool,
+}
+
+impl Iterator for RuleIter<'_> {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // DTSTART is always the first instance
+        let next = if self.emitted == 0 {
+            Some(self.dtstart)
+        } else {
+            self.next_from_rule()
+        };
+
+        match next {
+            Some(dt)
+                if self.until.is_none_or(|u| dt <= u)
+                    && self.rule.count.is_none_or(|c| self.emitted < c) =>
+            {
+                self.emitted += 1;
+                Some(dt)
+            }
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl RuleIter<'_> {
+    /// Skips the periods ending before `from`, instances before it may still come from
+    /// the period holding it. Rules with COUNT are counted from DTSTART and never skip.
+    pub fn seek(&mut self, from: NaiveDateTime) {
+        if self.rule.count.is_some() || from <= self.dtstart || self.emitted > 0 {
+            return;
+        }
+
+        // a period before, BYWEEKNO weeks may start in the year before theirs
+        let period = self.rule.periods_until(self.dtstart, from) - 1;
+        if period > 0 {
+            self.period = period as u64;
+            // DTSTART is before `from`
+            self.emitted = 1;
+        }
+    }
+
+    fn next_from_rule(&mut self) -> Option<NaiveDateTime> {
+        loop {
+            if let Some(dt) = self.buf.pop_front() {
+                return Some(dt);
+            }
+
+            if self.empty_periods > MAX_EMPTY_PERIODS {
+                return None;
+            }
+
+            let start = self.rule.period_start(self.dtstart, self.period)?;
+            self.period += 1;
+
+            let set = self.rule.expand_period(self.dtstart, start);
+
+            // instances of a period may come before its start (BYWEEKNO weeks starting in
+            // December), only a later instance tells UNTIL is passed
+            if let (Some(until), Some(first)) = (self.until, set.first())
+                && *first > until
+            {
+                return None;
+            }
+
+            self.buf
+                .extend(set.into_iter().filter(|dt| *dt > self.dtstart));
+
+            if self.buf.is_empty() {
+                self.empty_periods += 1;
+            } else {
+                self.empty_periods = 0;
+            }
+        }
+    }
+}
+
+// merges two sorted iterators, dropping duplicates
+struct MergeSorted<A: Iterator, B: Iterator> {
+    a: std::iter::Peekable<A>,
+    b: std::iter::Peekable<B>,
+    last: Option<DateTime<Utc>>,
+}
+
+impl<A, B> Iterator for MergeSorted<A, B>
+where
+    A: Iterator<Item = (DateTime<Utc>, EventTime)>,
+    B: Iterator<Item = (DateTime<Utc>, EventTime)>,
+{
+    type Item = (DateTime<Utc>, EventTime);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) if a.0 <= b.0 => self.a.next(),
+                (Some(_), Some(_)) => self.b.next(),
+                (Some(_), None) => self.a.next(),
+                (None, _) => self.b.next(),
+            }?;
+
+            if self.last != Some(next.0) {
+                self.last = Some(next.0);
+                return Some(next);
+            }
+        }
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "SECONDLY" => Frequency::Secondly,
+            "MINUTELY" => Frequency::Minutely,
+            "HOURLY" => Frequency::Hourly,
+            "DAILY" => Frequency::Daily,
+            "WEEKLY" => Frequency::Weekly,
+            "MONTHLY" => Frequency::Monthly,
+            "YEARLY" => Frequency::Yearly,
+            _ => bail!("Unknown FREQ: {}", s),
+        })
+    }
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+    Ok(match s {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => bail!("Unknown weekday: {}", s),
+    })
+}
+
+impl FromStr for WeekdayNum {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let split = s
+            .len()
+            .checked_sub(2)
+            .ok_or_else(|| anyhow!("Invalid BYDAY value: {}", s))?;
+        let (ord, wd) = s.split_at(split);
+
+        let ord = match ord {
+            "" => None,
+            ord => Some(ord.trim_start_matches('+').parse()?),
+        };
+
+        Ok(Self {
+            ord,
+            weekday: parse_weekday(wd)?,
+        })
+    }
+}
+
+impl FromStr for Until {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%SZ") {
+            Ok(Until::Utc(dt.and_utc()))
+        } else if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%S") {
+            Ok(Until::Floating(dt))
+        } else if let Ok(d) = NaiveDate::parse_from_str(s, "%Y%m%d") {
+            Ok(Until::Date(d))
+        } else {
+            Err(anyhow!("Invalid UNTIL: {}", s))
+        }
+    }
+}
+
+fn parse_list<T: FromStr>(v: &str) -> Result<Vec<T>>
+where
+    T::Err: fmt::Display,
+{
+    v.split(',')
+        .map(|x| {
+            x.parse::<T>()
+                .map_err(|e| anyhow!("Invalid value {}: {}", x, e))
+        })
+        .collect()
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut freq = None;
+        let mut rule = RecurrenceRule::new(Frequency::Daily);
+
+        for part in s.split(';').filter(|p| !p.is_empty()) {
+            let (k, v) = part
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid RRULE part: {}", part))?;
+
+            match k.to_ascii_uppercase().as_str() {
+                "FREQ" => freq = Some(v.parse()?),
+                "INTERVAL" => rule.interval = v.parse()?,
+                "COUNT" => rule.count = Some(v.parse()?),
+                "UNTIL" => rule.until = Some(v.parse()?),
+                "BYSECOND" => rule.by_second = parse_list(v)?,
+                "BYMINUTE" => rule.by_minute = parse_list(v)?,
+                "BYHOUR" => rule.by_hour = parse_list(v)?,
+                "BYDAY" => rule.by_day = parse_list(v)?,
+                "BYMONTHDAY" => rule.by_month_day = parse_list(v)?,
+                "BYYEARDAY" => rule.by_year_day = parse_list(v)?,
+                "BYWEEKNO" => rule.by_week_no = parse_list(v)?,
+                "BYMONTH" => rule.by_month = parse_list(v)?,
+                "BYSETPOS" => rule.by_set_pos = parse_list(v)?,
+                "WKST" => rule.wkst = parse_weekday(v)?,
+                name if name.starts_with("X-") => rule.x_parts.push((k.to_string(), v.to_string())),
+                _ => bail!("Unsupported RRULE part: {}", k),
+            }
+        }
+
+        rule.freq = freq.ok_or_else(|| anyhow!("Missing FREQ in RRULE: {}", s))?;
+        if rule.interval == 0 {
+            bail!("INTERVAL must be positive in RRULE: {}", s);
+        }
+        if rule.count.is_some() && rule.until.is_some() {
+            bail!("COUNT and UNTIL are mutually exclusive in RRULE: {}", s);
+        }
+
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Frequency::Secondly => "SECONDLY",
+            Frequency::Minutely => "MINUTELY",
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        })
+    }
+}
+
+fn weekday_code(wd: Weekday) -> &'static str {
+    match wd {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+impl fmt::Display for WeekdayNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ord) = self.ord {
+            write!(f, "{}", ord)?;
+        }
+        f.write_str(weekday_code(self.weekday))
+    }
+}
+
+impl fmt::Display for Until {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Until::Date(d) => write!(f, "{}", d.format("%Y%m%d")),
+            Until::Floating(dt) => write!(f, "{}", dt.format("%Y%m%dT%H%M%S")),
+            Until::Utc(dt) => write!(f, "{}", dt.format("%Y%m%dT%H%M%SZ")),
+        }
+    }
+}
+
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, name: &str, v: &[T]) -> fmt::Result {
+    if v.is_empty() {
+        return Ok(());
+    }
+
+    let values: Vec<String> = v.iter().map(T::to_string).collect();
+    write!(f, ";{}={}", name, values.join(","))
+}
+
+/// Formats the rule back as RRULE value, default parts are omitted
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.freq)?;
+
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until)?;
+        }
+
+        write_list(f, "BYSECOND", &self.by_second)?;
+        write_list(f, "BYMINUTE", &self.by_minute)?;
+        write_list(f, "BYHOUR", &self.by_hour)?;
+        write_list(f, "BYDAY", &self.by_day)?;
+        write_list(f, "BYMONTHDAY", &self.by_month_day)?;
+        write_list(f, "BYYEARDAY", &self.by_year_day)?;
+        write_list(f, "BYWEEKNO", &self.by_week_no)?;
+        write_list(f, "BYMONTH", &self.by_month)?;
+        write_list(f, "BYSETPOS", &self.by_set_pos)?;
+
+        if self.wkst != Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.wkst))?;
+        }
+        for (name, value) in &self.x_parts {
+            write!(f, ";{}={}", name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(y: i32, m: u32, d: u32, h: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, 0, 0)
+            .unwrap()
+    }
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    // instance starts of a rule, UTC UNTIL is taken on the same wall clock
+    fn expand(rule: &str, dtstart: NaiveDateTime) -> Vec<NaiveDateTime> {
+        let rule: RecurrenceRule = rule.parse().unwrap();
+        rule.iter(dtstart, |utc| utc.naive_utc()).take(50).collect()
+    }
+
+    fn days(rule: &str, dtstart: NaiveDateTime) -> Vec<NaiveDate> {
+        expand(rule, dtstart).iter().map(|dt| dt.date()).collect()
+    }
+
+    #[test]
+    fn count() {
+        assert_eq!(
+            days("FREQ=DAILY;COUNT=3", dt(2025, 1, 30, 9)),
+            [day(2025, 1, 30), day(2025, 1, 31), day(2025, 2, 1)]
+        );
+    }
+
+    #[test]
+    fn until_date_takes_the_whole_day() {
+        let start = dt(2025, 1, 1, 10);
+        assert_eq!(expand("FREQ=DAILY;UNTIL=20250103", start).len(), 3);
+        assert_eq!(expand("FREQ=DAILY;UNTIL=20250103T100000Z", start).len(), 3);
+        assert_eq!(expand("FREQ=DAILY;UNTIL=20250103T095959Z", start).len(), 2);
+    }
+
+    #[test]
+    fn by_day_ordinals() {
+        // last friday of the month
+        assert_eq!(
+            days("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3", dt(2025, 1, 31, 9)),
+            [day(2025, 1, 31), day(2025, 2, 28), day(2025, 3, 28)]
+        );
+        // second tuesday of the month
+        assert_eq!(
+            days("FREQ=MONTHLY;BYDAY=2TU;COUNT=3", dt(2025, 1, 14, 9)),
+            [day(2025, 1, 14), day(2025, 2, 11), day(2025, 3, 11)]
+        );
+    }
+
+    #[test]
+    fn by_set_pos() {
+        // last workday of the month
+        assert_eq!(
+            days(
+                "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;COUNT=3",
+                dt(2025, 1, 31, 9)
+            ),
+            [day(2025, 1, 31), day(2025, 2, 28), day(2025, 3, 31)]
+        );
+    }
+
+    #[test]
+    fn month_day_past_month_end_is_skipped() {
+        assert_eq!(
+            days("FREQ=MONTHLY;BYMONTHDAY=31;COUNT=4", dt(2025, 1, 31, 9)),
+            [
+                day(2025, 1, 31),
+                day(2025, 3, 31),
+                day(2025, 5, 31),
+                day(2025, 7, 31)
+            ]
+        );
+        // without BYMONTHDAY the day of DTSTART is used the same way
+        assert_eq!(
+            days("FREQ=MONTHLY;COUNT=2", dt(2025, 1, 31, 9)),
+            [day(2025, 1, 31), day(2025, 3, 31)]
+        );
+    }
+
+    #[test]
+    fn wkst_changes_biweekly_weeks() {
+        // RFC 5545 3.8.5.3 examples
+        let start = dt(1997, 8, 5, 9);
+        assert_eq!(
+            days("FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=MO", start),
+            [
+                day(1997, 8, 5),
+                day(1997, 8, 10),
+                day(1997, 8, 19),
+                day(1997, 8, 24)
+            ]
+        );
+        assert_eq!(
+            days("FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=SU", start),
+            [
+                day(1997, 8, 5),
+                day(1997, 8, 17),
+                day(1997, 8, 19),
+                day(1997, 8, 31)
+            ]
+        );
+    }
+
+    #[test]
+    fn by_year_day() {
+        assert_eq!(
+            days("FREQ=YEARLY;BYYEARDAY=1,-1;COUNT=4", dt(2024, 1, 1, 9)),
+            [
+                day(2024, 1, 1),
+                day(2024, 12, 31),
+                day(2025, 1, 1),
+                day(2025, 12, 31)
+            ]
+        );
+    }
+
+    #[test]
+    fn by_week_no() {
+        // RFC 5545 3.8.5.3: monday of week 20
+        assert_eq!(
+            days(
+                "FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO;COUNT=3",
+                dt(1997, 5, 12, 9)
+            ),
+            [day(1997, 5, 12), day(1998, 5, 11), day(1999, 5, 17)]
+        );
+        // week 1 of 2025 and of 2026 starts in the year before
+        assert_eq!(
+            days(
+                "FREQ=YEARLY;BYWEEKNO=1;BYDAY=MO;COUNT=2",
+                dt(2024, 12, 30, 9)
+            ),
+            [day(2024, 12, 30), day(2025, 12, 29)]
+        );
+        // UNTIL before the start of 2026 still takes its week 1
+        assert_eq!(
+            days(
+                "FREQ=YEARLY;BYWEEKNO=1;BYDAY=MO;UNTIL=20251230T000000Z",
+                dt(2024, 12, 30, 9)
+            ),
+            [day(2024, 12, 30), day(2025, 12, 29)]
+        );
+    }
+
+    #[test]
+    fn seek_skips_whole_periods() {
+        let rule: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH".parse().unwrap();
+        let start = dt(2000, 1, 4, 9);
+        let from = dt(2040, 3, 1, 0);
+
+        let mut iter = rule.iter(start, |utc| utc.naive_utc());
+        iter.seek(from);
+        // one period before the one of `from`, not the ~1000 since DTSTART
+        assert_eq!(iter.period, rule.periods_until(start, from) as u64 - 1);
+
+        let seeked: Vec<_> = iter.skip_while(|d| *d < from).take(4).collect();
+        let walked: Vec<_> = rule
+            .iter(start, |utc| utc.naive_utc())
+            .skip_while(|d| *d < from)
+            .take(4)
+            .collect();
+        assert_eq!(seeked, walked);
+        assert_eq!(seeked[0].date(), day(2040, 3, 6));
+    }
+
+    #[test]
+    fn seek_keeps_counted_rules() {
+        let rule: RecurrenceRule = "FREQ=DAILY;COUNT=3".parse().unwrap();
+        let mut iter = rule.iter(dt(2025, 1, 1, 9), |utc| utc.naive_utc());
+        iter.seek(dt(2025, 1, 3, 0));
+        assert_eq!(iter.count(), 3);
+    }
+
+    #[test]
+    fn seek_takes_week_1_of_the_year_after() {
+        // week 1 of 2026 starts in 2025
+        let rule: RecurrenceRule = "FREQ=YEARLY;BYWEEKNO=1;BYDAY=MO".parse().unwrap();
+        let mut iter = rule.iter(dt(2020, 12, 28, 9), |utc| utc.naive_utc());
+        iter.seek(dt(2025, 12, 1, 0));
+        assert_eq!(
+            iter.find(|d| *d >= dt(2025, 12, 1, 0)).map(|d| d.date()),
+            Some(day(2025, 12, 29))
+        );
+    }
+
+    #[test]
+    fn x_parts_are_kept() {
+        let rule: RecurrenceRule = "FREQ=WEEKLY;X-NAME=value;BYDAY=MO".parse().unwrap();
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;BYDAY=MO;X-NAME=value");
+        assert!("FREQ=DAILY;BYEASTER=0".parse::<RecurrenceRule>().is_err());
+    }
+
+    #[test]
+    fn rdates_and_exdates_are_merged() {
+        let utc = |d: u32| EventTime::Utc(dt(2025, 1, d, 10).and_utc());
+        let recurrence = Recurrence {
+            rule: Some("FREQ=WEEKLY;COUNT=3".parse().unwrap()),
+            // one extra day and one already produced by the rule
+            rdates: vec![utc(13), utc(8)],
+            exdates: vec![utc(13)],
+        };
+
+        let starts: Vec<DateTime<Utc>> = recurrence
+            .occurrences(&utc(6), &chrono_tz::UTC)
+            .map(|(start, _)| start)
+            .collect();
+        assert_eq!(starts, [6, 8, 20].map(|d| dt(2025, 1, d, 10).and_utc()));
+    }
+}
@@ -0,0 +1,158 @@
+// Reminder scheduler: fires event alarms as time passes
+//
+// Every check looks at alarms going off since the previous one, so alarms
+// are never fired twice and alarms from before the app started are skipped.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::common::{
+    alarm::AlarmTrigger,
+    calendar::{CalendarEvent, CalendarQuery, EventStatus},
+    index::CalendarIndex,
+};
+
+/// Alarm that went off
+#[derive(Debug, Clone)]
+pub struct Reminder {
+    /// event instance the alarm belongs to
+    pub event: CalendarEvent,
+    /// when the alarm went off (or goes off again after a snooze)
+    pub at: DateTime<Utc>,
+    /// DESCRIPTION of the alarm
+    pub text: Option<String>,
+}
+
+pub struct ReminderScheduler {
+    // alarms up to this instant were already fired
+    checked_until: DateTime<Utc>,
+    // fired and not dismissed yet, oldest first
+    active: Vec<Reminder>,
+    snoozed: Vec<Reminder>,
+}
+
+impl ReminderScheduler {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            checked_until: now,
+            active: vec![],
+            snoozed: vec![],
+        }
+    }
+
+    /// Fires alarms due since the last check, returns whether any did
+    pub fn check(&mut self, calendar: Option<&CalendarIndex>, now: DateTime<Utc>) -> bool {
+        if now <= self.checked_until {
+            return false;
+        }
+        let from = self.checked_until;
+        self.checked_until = now;
+
+        let (woken, snoozed): (Vec<_>, Vec<_>) = self.snoozed.drain(..).partition(|r| r.at <= now);
+        self.snoozed = snoozed;
+
+        let mut fired = woken;
+        if let Some(calendar) = calendar {
+            fired.extend(due(calendar, from, now));
+        }
+        fired.sort_by_key(|r| r.at);
+
+        let any = !fired.is_empty();
+        self.active.extend(fired);
+        any
+    }
+
+    /// Oldest reminder not dismissed yet
+    pub fn active(&self) -> Option<&Reminder> {
+        self.active.first()
+    }
+
+    /// Number of reminders not dismissed yet
+    pub fn pending(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn dismiss(&mut self) {
+        if !self.active.is_empty() {
+            self.active.remove(0);
+        }
+    }
+
+    /// Hides the active reminder, it goes off again `duration` after `now`
+    pub fn snooze(&mut self, now: DateTime<Utc>, duration: Duration) {
+        if !self.active.is_empty() {
+            let mut reminder = self.active.remove(0);
+            reminder.at = now + duration;
+            self.snoozed.push(reminder);
+        }
+    }
+}
+
+// reminders of alarms going off in `(from, to]`
+fn due(calendar: &CalendarIndex, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Reminder> {
+    let tz = calendar.tz();
+    let in_window = |at: &DateTime<Utc>| from < *at && *at <= to;
+    let mut reminders = vec![];
+
+    let events = calendar
+        .calendar()
+        .events
+        .iter()
+        .flat_map(|e| std::iter::once(e).chain(&e.overrides));
+
+    // how far from an instance its relative alarms may go off
+    let mut reach = Duration::zero();
+    for event in events {
+        for alarm in event.alarms.iter().filter(|a| a.is_reminder()) {
+            match alarm.trigger {
+                AlarmTrigger::Relative { offset, .. } => {
+                    let repeats = alarm.interval.map(|i| i.to_duration()).unwrap_or_default()
+                        * alarm.repeat as i32;
+                    reach = reach.max(offset.to_duration().abs() + repeats.abs());
+                }
+                // absolute alarms don't move with instances
+                AlarmTrigger::Absolute(_) => {
+                    if event.status == EventStatus::Cancelled {
+                        continue;
+                    }
+                    reminders.extend(
+                        alarm
+                            .fire_times(event, tz)
+                            .into_iter()
+                            .filter(in_window)
+                            .map(|at| Reminder {
+                                event: event.clone(),
+                                at,
+                                text: alarm.description.clone(),
+                            }),
+                    );
+                }
+            }
+        }
+    }
+
+    for event in calendar.events_in_range(from - reach, to + reach) {
+        if event.status == EventStatus::Cancelled {
+            continue;
+        }
+
+        for alarm in &event.alarms {
+            if !alarm.is_reminder() || matches!(alarm.trigger, AlarmTrigger::Absolute(_)) {
+                continue;
+            }
+
+            reminders.extend(
+                alarm
+                    .fire_times(&event, tz)
+                    .into_iter()
+                    .filter(in_window)
+                    .map(|at| Reminder {
+                        event: event.clone(),
+                        at,
+                        text: alarm.description.clone(),
+                    }),
+            );
+        }
+    }
+
+    reminders
+}
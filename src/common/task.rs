@@ -0,0 +1,101 @@
+// Tasks (VTODO), RFC 5545 3.6.2
+
+use std::{convert::Infallible, str::FromStr};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+use crate::common::{
+    alarm::Alarm,
+    calendar::{RawComponent, RawProperty},
+    time::EventTime,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    pub uid: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub due: Option<EventTime>,
+    /// 1 is the highest, 9 the lowest, 0 means undefined
+    pub priority: u8,
+    pub status: TaskStatus,
+    /// PERCENT-COMPLETE
+    pub percent_complete: Option<u8>,
+    /// when the task was completed
+    pub completed: Option<DateTime<Utc>>,
+    /// UID of the parent task (RELATED-TO, RELTYPE=PARENT)
+    pub parent: Option<String>,
+    /// parameters of the parent's RELATED-TO as read, e.g. RELTYPE
+    pub parent_params: Vec<(String, Vec<String>)>,
+    /// DTSTAMP as read, written back; the time of writing is used when missing
+    pub dtstamp: Option<DateTime<Utc>>,
+    /// properties not understood yet, written back as read
+    pub extra: Vec<RawProperty>,
+    pub alarms: Vec<Alarm>,
+    /// sub-components not understood yet, written back as read
+    pub components: Vec<RawComponent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskStatus {
+    NeedsAction,
+    InProcess,
+    Completed,
+    Cancelled,
+    Unknown,
+}
+
+impl Task {
+    pub fn is_done(&self) -> bool {
+        self.status == TaskStatus::Completed || self.completed.is_some()
+    }
+
+    /// Day the task is due on in `local` zone
+    pub fn due_day(&self, local: &Tz) -> Option<NaiveDate> {
+        self.due.as_ref().map(|due| match due {
+            EventTime::Date(d) => *d,
+            due => due.in_zone(local).date_naive(),
+        })
+    }
+
+    /// Not done and due before `now`, a due date counts until its end
+    pub fn is_overdue(&self, now: DateTime<Utc>, local: &Tz) -> bool {
+        if self.is_done() || self.status == TaskStatus::Cancelled {
+            return false;
+        }
+
+        match &self.due {
+            Some(EventTime::Date(d)) => *d < now.with_timezone(local).date_naive(),
+            Some(due) => due.utc(local) < now,
+            None => false,
+        }
+    }
+}
+
+impl TaskStatus {
+    /// STATUS value, `None` when the task had no (known) status
+    pub fn as_ical(&self) -> Option<&'static str> {
+        match self {
+            TaskStatus::NeedsAction => Some("NEEDS-ACTION"),
+            TaskStatus::InProcess => Some("IN-PROCESS"),
+            TaskStatus::Completed => Some("COMPLETED"),
+            TaskStatus::Cancelled => Some("CANCELLED"),
+            TaskStatus::Unknown => None,
+        }
+    }
+}
+
+impl FromStr for TaskStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "NEEDS-ACTION" => TaskStatus::NeedsAction,
+            "IN-PROCESS" => TaskStatus::InProcess,
+            "COMPLETED" => TaskStatus::Completed,
+            "CANCELLED" => TaskStatus::Cancelled,
+            _ => TaskStatus::Unknown,
+        })
+    }
+}
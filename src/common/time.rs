@@ -0,0 +1,479 @@
+// Event date-times: floating, UTC or bound to a TZID (IANA or custom VTIMEZONE)
+
+use std::{fmt, ops::RangeInclusive, str::FromStr, sync::Arc};
+
+use anyhow::{Result, anyhow};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Utc,
+};
+use chrono_tz::{OffsetComponents, OffsetName, Tz, TzOffset};
+
+use crate::common::{calendar::RawProperty, recurrence::RecurrenceRule};
+
+// custom timezone transitions are precomputed up to this year
+const MAX_TRANSITION_YEAR: i32 = 2200;
+
+/// STANDARD or DAYLIGHT sub-component of a VTIMEZONE
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observance {
+    pub daylight: bool,
+    pub start: NaiveDateTime,
+    pub offset_from: FixedOffset,
+    pub offset_to: FixedOffset,
+    pub rule: Option<RecurrenceRule>,
+    pub rdates: Vec<NaiveDateTime>,
+    pub name: Option<String>,
+    /// other properties (e.g. COMMENT), kept for writing
+    pub extra: Vec<RawProperty>,
+}
+
+/// Timezone defined by a VTIMEZONE block of the file
+#[derive(Debug, Clone)]
+pub struct VTimezone {
+    pub tzid: String,
+    pub observances: Vec<Observance>,
+    /// other properties of the block (e.g. X-LIC-LOCATION), kept for writing
+    pub extra: Vec<RawProperty>,
+
+    // (utc instant, offset in effect from that instant), sorted
+    transitions: Vec<(DateTime<Utc>, FixedOffset)>,
+    initial: FixedOffset,
+}
+
+impl VTimezone {
+    pub fn new(tzid: String, observances: Vec<Observance>) -> Self {
+        let mut transitions: Vec<(DateTime<Utc>, FixedOffset)> = observances
+            .iter()
+            .flat_map(|o| {
+                let onsets: Box<dyn Iterator<Item = NaiveDateTime>> = match &o.rule {
+                    Some(rule) => Box::new(
+                        rule.iter(o.start, move |utc| utc.naive_utc() + o.offset_from)
+                            .take_while(|dt| dt.year() <= MAX_TRANSITION_YEAR),
+                    ),
+                    None => Box::new(std::iter::once(o.start)),
+                };
+
+                onsets
+                    .chain(o.rdates.iter().copied())
+                    .map(|local| ((local - o.offset_from).and_utc(), o.offset_to))
+            })
+            .collect();
+        transitions.sort_by_key(|(at, _)| *at);
+
+        let initial = observances
+            .iter()
+            .min_by_key(|o| o.start)
+            .map(|o| o.offset_from)
+            .unwrap_or(Utc.fix());
+
+        Self {
+            tzid,
+            observances,
+            extra: vec![],
+            transitions,
+            initial,
+        }
+    }
+
+    /// Same zone as the IANA one for `years`, its transitions are listed as RDATEs
+    pub fn from_iana(tz: Tz, years: RangeInclusive<i32>) -> Self {
+        let offset_at = |utc: DateTime<Utc>| tz.offset_from_utc_datetime(&utc.naive_utc());
+        let new_year = |year: i32| {
+            NaiveDate::from_yo_opt(year, 1)
+                .unwrap_or(NaiveDate::MIN)
+                .and_time(NaiveTime::MIN)
+                .and_utc()
+        };
+
+        let mut observances: Vec<Observance> = vec![];
+        let onset = |observances: &mut Vec<Observance>, at: DateTime<Utc>, from, to: TzOffset| {
+            let daylight = !to.dst_offset().is_zero();
+            let name = to.abbreviation().map(str::to_string);
+            let start = at.naive_utc() + from;
+
+            // same kind of change every year, one observance with the later ones as RDATEs
+            match observances.iter_mut().find(|o| {
+                o.daylight == daylight
+                    && o.offset_from == from
+                    && o.offset_to == to.fix()
+                    && o.name == name
+            }) {
+                Some(o) => o.rdates.push(start),
+                None => observances.push(Observance {
+                    daylight,
+                    start,
+                    offset_from: from,
+                    offset_to: to.fix(),
+                    rule: None,
+                    rdates: vec![],
+                    name,
+                    extra: vec![],
+                }),
+            }
+        };
+
+        let (start, end) = (new_year(*years.start()), new_year(*years.end() + 1));
+        let mut current = offset_at(start);
+        onset(&mut observances, start, current.fix(), current);
+
+        // zones change at most once a day, the exact second is searched for
+        let mut day = start;
+        while day < end {
+            let next_day = day + Duration::days(1);
+            let next = offset_at(next_day);
+
+            if next.fix() != current.fix() {
+                let (mut before, mut after) = (day, next_day);
+                while after - before > Duration::seconds(1) {
+                    let mid = before + (after - before) / 2;
+                    if offset_at(mid).fix() == current.fix() {
+                        before = mid;
+                    } else {
+                        after = mid;
+                    }
+                }
+
+                onset(&mut observances, after, current.fix(), next);
+                current = next;
+            }
+
+            day = next_day;
+        }
+
+        Self::new(tz.name().to_string(), observances)
+    }
+
+    fn offset_at(&self, utc: DateTime<Utc>) -> FixedOffset {
+        let idx = self.transitions.partition_point(|(at, _)| *at <= utc);
+        if idx == 0 {
+            self.initial
+        } else {
+            self.transitions[idx - 1].1
+        }
+    }
+
+    fn offsets(&self) -> Vec<FixedOffset> {
+        let mut offsets: Vec<FixedOffset> = std::iter::once(self.initial)
+            .chain(self.transitions.iter().map(|(_, o)| *o))
+            .collect();
+        offsets.sort_by_key(|o| o.local_minus_utc());
+        offsets.dedup();
+        offsets
+    }
+}
+
+impl PartialEq for VTimezone {
+    fn eq(&self, other: &Self) -> bool {
+        self.tzid == other.tzid
+            && self.observances == other.observances
+            && self.extra == other.extra
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Zone {
+    Iana(Tz),
+    Custom(Arc<VTimezone>),
+}
+
+impl Zone {
+    pub fn name(&self) -> &str {
+        match self {
+            Zone::Iana(tz) => tz.name(),
+            Zone::Custom(vtz) => &vtz.tzid,
+        }
+    }
+
+    /// Wall clock time -> instant, earliest one for ambiguous times,
+    /// times skipped by DST are shifted forward
+    pub fn from_local(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            Zone::Iana(tz) => resolve_local(tz, local),
+            Zone::Custom(vtz) => {
+                let offsets = vtz.offsets();
+                offsets
+                    .iter()
+                    .rev()
+                    .map(|o| (local - *o).and_utc())
+                    .find(|utc| utc.naive_utc() + vtz.offset_at(*utc) == local)
+                    .unwrap_or_else(|| {
+                        // in a gap: taken with the offset before it, which lands after it
+                        let latest = offsets.last().copied().unwrap_or(vtz.initial);
+                        (local - vtz.offset_at((local - latest).and_utc())).and_utc()
+                    })
+            }
+        }
+    }
+
+    /// Instant -> wall clock time
+    pub fn to_local(&self, utc: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Iana(tz) => utc.with_timezone(tz).naive_local(),
+            Zone::Custom(vtz) => utc.naive_utc() + vtz.offset_at(utc),
+        }
+    }
+}
+
+/// Value of DTSTART/DTEND/RDATE/EXDATE/RECURRENCE-ID
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventTime {
+    /// whole day (VALUE=DATE), starts at midnight wherever the user is
+    Date(NaiveDate),
+    /// no timezone, same wall clock time wherever the user is
+    Floating(NaiveDateTime),
+    Utc(DateTime<Utc>),
+    Zoned(NaiveDateTime, Zone),
+}
+
+impl EventTime {
+    /// Instant of this time, floating times are taken in `local` zone
+    pub fn utc(&self, local: &Tz) -> DateTime<Utc> {
+        match self {
+            EventTime::Date(d) => resolve_local(local, d.and_time(NaiveTime::MIN)),
+            EventTime::Floating(dt) => resolve_local(local, *dt),
+            EventTime::Utc(dt) => *dt,
+            EventTime::Zoned(dt, zone) => zone.from_local(*dt),
+        }
+    }
+
+    pub fn in_zone(&self, local: &Tz) -> DateTime<Tz> {
+        self.utc(local).with_timezone(local)
+    }
+
+    pub fn is_date(&self) -> bool {
+        matches!(self, EventTime::Date(_))
+    }
+
+    /// Wall clock time as written in the file
+    pub fn naive(&self) -> NaiveDateTime {
+        match self {
+            EventTime::Date(d) => d.and_time(NaiveTime::MIN),
+            EventTime::Floating(dt) | EventTime::Zoned(dt, _) => *dt,
+            EventTime::Utc(dt) => dt.naive_utc(),
+        }
+    }
+
+    /// Same kind of time (and zone) with another wall clock value
+    pub fn with_naive(&self, naive: NaiveDateTime) -> EventTime {
+        match self {
+            EventTime::Date(_) => EventTime::Date(naive.date()),
+            EventTime::Floating(_) => EventTime::Floating(naive),
+            EventTime::Utc(_) => EventTime::Utc(naive.and_utc()),
+            EventTime::Zoned(_, zone) => EventTime::Zoned(naive, zone.clone()),
+        }
+    }
+
+    /// Converts an instant into the wall clock of this time
+    pub fn wall_clock_of(&self, utc: DateTime<Utc>, local: &Tz) -> NaiveDateTime {
+        match self {
+            EventTime::Date(_) | EventTime::Floating(_) => utc.with_timezone(local).naive_local(),
+            EventTime::Utc(_) => utc.naive_utc(),
+            EventTime::Zoned(_, zone) => zone.to_local(utc),
+        }
+    }
+
+    pub fn same_zone(&self, other: &EventTime) -> bool {
+        match (self, other) {
+            (EventTime::Date(_), EventTime::Date(_)) => true,
+            (EventTime::Floating(_), EventTime::Floating(_)) => true,
+            (EventTime::Utc(_), EventTime::Utc(_)) => true,
+            (EventTime::Zoned(_, a), EventTime::Zoned(_, b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Time `duration` after this one, days are added on the wall clock; `None` past the
+    /// representable dates
+    pub fn plus(&self, duration: &EventDuration) -> Option<EventTime> {
+        let days = Duration::try_days(duration.days)?;
+
+        Some(match self {
+            EventTime::Date(d) => EventTime::Date(d.checked_add_signed(days)?),
+            EventTime::Floating(dt) => EventTime::Floating(
+                dt.checked_add_signed(days)?
+                    .checked_add_signed(duration.time)?,
+            ),
+            EventTime::Utc(dt) => EventTime::Utc(
+                dt.checked_add_signed(days)?
+                    .checked_add_signed(duration.time)?,
+            ),
+            EventTime::Zoned(dt, zone) => EventTime::Zoned(
+                zone.to_local(
+                    zone.from_local(dt.checked_add_signed(days)?)
+                        .checked_add_signed(duration.time)?,
+                ),
+                zone.clone(),
+            ),
+        })
+    }
+
+    /// Length of `[self, end)`, on the wall clock when both are in the same zone
+    pub fn duration_until(&self, end: &EventTime, local: &Tz) -> Duration {
+        if self.same_zone(end) {
+            end.naive() - self.naive()
+        } else {
+            end.utc(local) - self.utc(local)
+        }
+    }
+}
+
+/// DURATION value, e.g. `PT1H30M`, `P1D` or `-PT15M`.
+/// Days and weeks are nominal (follow the wall clock across DST), the rest is exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventDuration {
+    pub days: i64,
+    pub time: Duration,
+}
+
+impl EventDuration {
+    /// Exact length, days taken as 24 hours
+    pub fn to_duration(&self) -> Duration {
+        Duration::days(self.days) + self.time
+    }
+}
+
+impl FromStr for EventDuration {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let err = || anyhow!("Invalid duration: {}", s);
+
+        let (sign, rest) = match s.split_at_checked(1).ok_or_else(err)? {
+            ("-", rest) => (-1, rest),
+            ("+", rest) => (1, rest),
+            _ => (1, s),
+        };
+        let rest = rest.strip_prefix('P').ok_or_else(err)?;
+
+        let mut days: i64 = 0;
+        let mut secs: i64 = 0;
+        let mut in_time = false;
+        // parts since start or `T`, both need at least one
+        let mut parts = 0;
+        let mut num = String::new();
+
+        for c in rest.chars() {
+            match c {
+                '0'..='9' => num.push(c),
+                'T' if !in_time && num.is_empty() => {
+                    in_time = true;
+                    parts = 0;
+                }
+                _ => {
+                    let n: i64 = num.parse().map_err(|_| err())?;
+                    num.clear();
+
+                    let (total, unit) = match (in_time, c) {
+                        (false, 'W') => (&mut days, 7),
+                        (false, 'D') => (&mut days, 1),
+                        (true, 'H') => (&mut secs, 3600),
+                        (true, 'M') => (&mut secs, 60),
+                        (true, 'S') => (&mut secs, 1),
+                        _ => return Err(err()),
+                    };
+                    *total = n
+                        .checked_mul(unit)
+                        .and_then(|n| total.checked_add(n))
+                        .ok_or_else(err)?;
+                    parts += 1;
+                }
+            }
+        }
+        if !num.is_empty() || parts == 0 {
+            return Err(err());
+        }
+
+        // the whole length has to fit a `Duration`
+        let time = Duration::try_seconds(sign * secs).ok_or_else(err)?;
+        Duration::try_days(sign * days)
+            .and_then(|d| d.checked_add(&time))
+            .ok_or_else(err)?;
+
+        Ok(Self {
+            days: sign * days,
+            time,
+        })
+    }
+}
+
+impl fmt::Display for EventDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.time.num_seconds();
+        if self.days < 0 || secs < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+
+        let days = self.days.abs();
+        let secs = secs.abs();
+
+        if secs == 0 && days != 0 && days % 7 == 0 {
+            return write!(f, "{}W", days / 7);
+        }
+        if days != 0 {
+            write!(f, "{}D", days)?;
+        }
+        if secs != 0 || days == 0 {
+            f.write_str("T")?;
+            let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+            if h != 0 {
+                write!(f, "{}H", h)?;
+            }
+            if m != 0 {
+                write!(f, "{}M", m)?;
+            }
+            if s != 0 || (h == 0 && m == 0) {
+                write!(f, "{}S", s)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn resolve_local<T: TimeZone>(tz: &T, local: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+/// Parses UTC offset of VTIMEZONE, e.g. `+0200`, `-0500` or `+053000`
+pub fn parse_utc_offset(s: &str) -> Result<FixedOffset> {
+    let err = || anyhow!("Invalid UTC offset: {}", s);
+
+    let (sign, digits) = match s.split_at_checked(1).ok_or_else(err)? {
+        ("+", d) => (1, d),
+        ("-", d) => (-1, d),
+        _ => return Err(err()),
+    };
+
+    if !(digits.len() == 4 || digits.len() == 6) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(err());
+    }
+
+    let h: i32 = digits[0..2].parse()?;
+    let m: i32 = digits[2..4].parse()?;
+    let sec: i32 = digits.get(4..6).map_or(Ok(0), i32::from_str)?;
+
+    FixedOffset::east_opt(sign * (h * 3600 + m * 60 + sec)).ok_or_else(err)
+}
+
+/// Formats UTC offset for VTIMEZONE, seconds are only written when present
+pub fn format_utc_offset(offset: FixedOffset) -> String {
+    let total = offset.local_minus_utc();
+    let sign = if total < 0 { '-' } else { '+' };
+    let total = total.abs();
+
+    let (h, m, s) = (total / 3600, total % 3600 / 60, total % 60);
+    if s == 0 {
+        format!("{}{:02}{:02}", sign, h, m)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, h, m, s)
+    }
+}
@@ -0,0 +1,208 @@
+// User configuration, read from `<config dir>/taskrs/config`
+//
+// Format is one `key = value` per line. A line starting with `#` is a comment,
+// so is the rest of a line from a `#` after a blank; `#` inside a value is kept:
+//
+//     timezone = Europe/Kyiv
+//     hide_cancelled = true
+//     email = me@example.com
+//     only_accepted = true
+//     snooze_minutes = 5
+//     tick_rate_ms = 250
+//     journal = /home/me/notes/journal.ics
+//     day_start = 8
+//     day_end = 20
+//     slot_minutes = 30
+//     first_weekday = sunday
+
+use std::{fs, path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Weekday;
+use chrono_tz::Tz;
+
+pub struct Config {
+    /// zone events are displayed in, floating times are taken in it too
+    pub timezone: Tz,
+    /// leave cancelled events out instead of drawing them struck-through
+    pub hide_cancelled: bool,
+    /// user's own address, to find them among attendees
+    pub email: Option<String>,
+    /// show only events `email` organizes or accepted
+    pub only_accepted: bool,
+    /// how long a snoozed reminder stays hidden
+    pub snooze_minutes: u32,
+    /// how often the screen refreshes and reminders are checked without input
+    pub tick_rate: Duration,
+    /// calendar file journal entries are kept in, none without a data dir
+    pub journal_path: Option<PathBuf>,
+    /// first hour shown in the daily timeline
+    pub day_start: u32,
+    /// hour the daily timeline ends at, 24 for midnight
+    pub day_end: u32,
+    /// length of one timeline row, divides an hour
+    pub slot_minutes: u32,
+    /// day the weekly view starts with
+    pub first_weekday: Weekday,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            timezone: system_timezone().unwrap_or(Tz::UTC),
+            hide_cancelled: false,
+            email: None,
+            only_accepted: false,
+            snooze_minutes: 5,
+            tick_rate: Duration::from_millis(250),
+            journal_path: dirs::data_dir().map(|d| d.join("taskrs").join("journal.ics")),
+            day_start: 0,
+            day_end: 24,
+            slot_minutes: 30,
+            first_weekday: Weekday::Mon,
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("taskrs").join("config"))
+    }
+
+    /// Loads config file, defaults are used if it does not exist
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) if path.exists() => {
+                let contents = fs::read_to_string(&path)?;
+                Self::parse(&contents).with_context(|| format!("Invalid config {}", path.display()))
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut config = Self::default();
+
+        for (n, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .map(|(k, v)| (k.trim(), strip_comment(v.trim())))
+                .ok_or_else(|| anyhow!("Line {}: expected `key = value`", n + 1))?;
+
+            config
+                .set(key, value)
+                .with_context(|| format!("Line {}", n + 1))?;
+        }
+
+        if config.only_accepted && config.email.is_none() {
+            bail!("only_accepted requires email");
+        }
+        if config.day_start >= config.day_end {
+            bail!("day_start must be before day_end");
+        }
+
+        Ok(config)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "timezone" => {
+                self.timezone =
+                    Tz::from_str(value).map_err(|_| anyhow!("Unknown timezone: {}", value))?
+            }
+            "hide_cancelled" => self.hide_cancelled = parse_bool(value)?,
+            "email" => self.email = Some(value.to_string()),
+            "only_accepted" => self.only_accepted = parse_bool(value)?,
+            "snooze_minutes" => {
+                self.snooze_minutes = value
+                    .parse()
+                    .map_err(|_| anyhow!("Expected number of minutes: {}", value))?
+            }
+            "tick_rate_ms" => {
+                let ms = value.parse().ok().filter(|ms| *ms > 0).ok_or_else(|| {
+                    anyhow!("Expected positive number of milliseconds: {}", value)
+                })?;
+                self.tick_rate = Duration::from_millis(ms);
+            }
+            "journal" => self.journal_path = Some(PathBuf::from(value)),
+            "day_start" => self.day_start = parse_hour(value)?,
+            "day_end" => self.day_end = parse_hour(value)?,
+            "slot_minutes" => {
+                self.slot_minutes = value
+                    .parse()
+                    .ok()
+                    .filter(|m| *m > 0 && 60 % m == 0)
+                    .ok_or_else(|| {
+                        anyhow!("Expected number of minutes dividing an hour: {}", value)
+                    })?
+            }
+            "first_weekday" => {
+                self.first_weekday = value
+                    .parse()
+                    .map_err(|_| anyhow!("Expected day of the week: {}", value))?
+            }
+            _ => bail!("Unknown key: {}", key),
+        }
+
+        Ok(())
+    }
+}
+
+// value without a trailing comment, one starts at a `#` after a blank
+fn strip_comment(value: &str) -> &str {
+    value
+        .char_indices()
+        .find(|&(i, c)| c == '#' && value[..i].ends_with(char::is_whitespace))
+        .map_or(value, |(i, _)| value[..i].trim_end())
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("Expected true or false: {}", value))
+}
+
+fn parse_hour(value: &str) -> Result<u32> {
+    value
+        .parse()
+        .ok()
+        .filter(|h| *h <= 24)
+        .ok_or_else(|| anyhow!("Expected hour from 0 to 24: {}", value))
+}
+
+fn system_timezone() -> Option<Tz> {
+    std::env::var("TZ")
+        .ok()
+        .or_else(|| iana_time_zone::get_timezone().ok())
+        .and_then(|name| Tz::from_str(name.trim_start_matches(':')).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_inside_a_value_is_kept() {
+        let config = Config::parse(
+            "# notes\n\
+             journal = ~/notes#2.ics\n\
+             email = me@example.com   # work address\n\
+             \t# indented comment\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.journal_path, Some(PathBuf::from("~/notes#2.ics")));
+        assert_eq!(config.email.as_deref(), Some("me@example.com"));
+    }
+
+    #[test]
+    fn value_may_start_with_a_hash() {
+        let config = Config::parse("email = #team@example.com\n").unwrap();
+        assert_eq!(config.email.as_deref(), Some("#team@example.com"));
+    }
+}
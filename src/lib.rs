@@ -1,4 +1,7 @@
 pub mod app;
-pub mod calendar;
+pub mod common;
+pub mod config;
+pub mod message;
+pub mod providers;
 pub mod state;
 pub mod ui;
@@ -0,0 +1,83 @@
+// Messages driving the app loop
+//
+// Terminal input and ticks come from a background thread, anything else
+// (loaders, timers) can push messages through a cloned sender. The ui thread
+// only waits on the channel, so it wakes up for whichever comes first.
+
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event};
+
+use crate::common::calendar::Calendar;
+
+pub enum AppMessage {
+    /// sent every tick, time-based state (today, reminders) is refreshed on it
+    Tick,
+    Input(Event),
+    /// reading the terminal failed, no more input is coming
+    InputFailed(anyhow::Error),
+    /// background load of a calendar file finished
+    CalendarLoaded {
+        path: PathBuf,
+        result: Result<Calendar>,
+    },
+}
+
+pub struct MessageLoop {
+    tx: Sender<AppMessage>,
+    rx: Receiver<AppMessage>,
+}
+
+impl MessageLoop {
+    /// Starts reading terminal input, with a tick every `tick_rate`
+    pub fn new(tick_rate: Duration) -> Self {
+        let (tx, rx) = mpsc::channel();
+
+        let input_tx = tx.clone();
+        thread::spawn(move || {
+            if let Err(e) = read_input(&input_tx, tick_rate) {
+                // nobody to tell when the app is gone already
+                let _ = input_tx.send(AppMessage::InputFailed(e));
+            }
+        });
+
+        Self { tx, rx }
+    }
+
+    pub fn sender(&self) -> Sender<AppMessage> {
+        self.tx.clone()
+    }
+
+    /// Waits for the next message
+    pub fn next(&self) -> Result<AppMessage> {
+        Ok(self.rx.recv()?)
+    }
+
+    /// Next message if one is already waiting
+    pub fn try_next(&self) -> Option<AppMessage> {
+        self.rx.try_recv().ok()
+    }
+}
+
+// runs until the receiving side is gone or the terminal fails
+fn read_input(tx: &Sender<AppMessage>, tick_rate: Duration) -> Result<()> {
+    let mut last_tick = Instant::now();
+
+    loop {
+        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)? {
+            tx.send(AppMessage::Input(event::read()?))?;
+        }
+
+        if last_tick.elapsed() >= tick_rate {
+            tx.send(AppMessage::Tick)?;
+            last_tick = Instant::now();
+        }
+    }
+}
@@ -1,15 +1,36 @@
 use anyhow::Result;
-use std::path::PathBuf;
+use std::{fs, io::BufRead, path::PathBuf};
 
 use crate::{
-    common::calendar::{Calendar, CalendarSource},
-    providers::gcal::src::GcalSrc,
+    common::calendar::{Calendar, CalendarEvent, CalendarSource, CalendarWriter, IcalParser},
+    providers::gcal::{parser::GcalParser, src::GcalSrc, writer::GcalWriter},
 };
 
 mod parser;
 mod src;
+mod writer;
 
 pub fn import_gcal(path: PathBuf) -> Result<Calendar> {
     let src = GcalSrc::new(path);
     src.load()
 }
+
+pub fn export_gcal(calendar: &Calendar, path: PathBuf) -> Result<()> {
+    fs::write(path, write_ics(calendar)?)?;
+    Ok(())
+}
+
+/// Parse iCalendar data, e.g. a whole .ics file
+pub fn parse_ics<B: BufRead>(r: B) -> Result<Calendar> {
+    GcalParser::new().parse(r)
+}
+
+/// Serialize calendar as iCalendar (RFC 5545)
+pub fn write_ics(calendar: &Calendar) -> Result<String> {
+    GcalWriter::new().write(calendar)
+}
+
+/// Serialize one event as a VEVENT, as it would be written in a calendar
+pub fn write_event_ics(event: &CalendarEvent) -> String {
+    GcalWriter::new().write_event(event)
+}
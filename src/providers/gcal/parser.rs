@@ -1,10 +1,28 @@
-use chrono::{DateTime, TimeZone, Utc};
-use ical::parser::ical;
-use std::{io::BufRead, str::FromStr};
+use ::ical::parser::ical::{
+    self,
+    component::{IcalAlarm, IcalJournal, IcalTimeZone, IcalTimeZoneTransitionType, IcalTodo},
+};
+use ::ical::property::Property;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use std::{collections::HashMap, io::BufRead, str::FromStr, sync::Arc};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 
-use crate::common::calendar::{Calendar, CalendarEvent, EventStatus, IcalParser};
+use crate::common::{
+    alarm::{Alarm, AlarmAction, AlarmTrigger},
+    calendar::{
+        Attendee, Calendar, CalendarEvent, EventStatus, IcalParser, Organizer, PartStat,
+        RawComponent, RawProperty, Role,
+    },
+    journal::JournalEntry,
+    recurrence::{Recurrence, RecurrenceRule},
+    task::{Task, TaskStatus},
+    time::{EventDuration, EventTime, Observance, VTimezone, Zone, parse_utc_offset},
+};
+
+// custom timezones of the file by TZID
+type Timezones = HashMap<String, Arc<VTimezone>>;
 
 pub(super) struct GcalParser {}
 
@@ -17,11 +35,72 @@ impl GcalParser {
 impl<B: BufRead> IcalParser<B> for GcalParser {
     fn parse(&self, r: B) -> anyhow::Result<Calendar> {
         let parser = ical::IcalParser::new(r);
+        let mut name = None;
         let mut events = Vec::new();
+        let mut tasks = Vec::new();
+        let mut journals = Vec::new();
+        let mut all_timezones: Vec<Arc<VTimezone>> = vec![];
+        let mut extra = vec![];
+        let mut components = vec![];
 
         for calendar in parser {
             let calendar = calendar?;
 
+            for prop in calendar.properties {
+                match prop.name.as_str() {
+                    "X-WR-CALNAME" => name = prop.value.as_deref().map(unescape_text),
+                    // one of each for the whole file, the first one
+                    "VERSION" | "PRODID" | "CALSCALE"
+                        if extra.iter().any(|p: &RawProperty| p.name == prop.name) => {}
+                    _ => extra.push(raw_property(prop)),
+                }
+            }
+
+            components.extend(
+                calendar
+                    .free_busys
+                    .into_iter()
+                    .map(|f| raw_component("VFREEBUSY", f.properties, vec![])),
+            );
+            components.extend(alarm_components(calendar.alarms));
+
+            let parsed: Vec<Arc<VTimezone>> = calendar
+                .timezones
+                .iter()
+                .map(|tz| parse_timezone(tz).map(Arc::new))
+                .collect::<Result<_>>()?;
+            let timezones: Timezones = parsed
+                .iter()
+                .map(|tz| (tz.tzid.clone(), tz.clone()))
+                .collect();
+
+            for tz in parsed {
+                if !all_timezones.iter().any(|t| t.tzid == tz.tzid) {
+                    all_timezones.push(tz);
+                }
+            }
+
+            // tasks we can't make sense of are kept as they are
+            for todo in calendar.todos {
+                match parse_task(&todo, &timezones) {
+                    Ok(task) => tasks.push(task),
+                    Err(_) => components.push(raw_component(
+                        "VTODO",
+                        todo.properties,
+                        alarm_components(todo.alarms),
+                    )),
+                }
+            }
+
+            for journal in calendar.journals {
+                match parse_journal(&journal, &timezones) {
+                    Ok(entry) => journals.push(entry),
+                    Err(_) => {
+                        components.push(raw_component("VJOURNAL", journal.properties, vec![]))
+                    }
+                }
+            }
+
             for component in calendar.events {
                 let mut uid = None;
                 let mut summary = None;
@@ -29,30 +108,93 @@ impl<B: BufRead> IcalParser<B> for GcalParser {
                 let mut location = None;
                 let mut dtstart = None;
                 let mut dtend = None;
+                let mut duration = None;
                 let mut status = None;
+                let mut recurrence_id = None;
+                let mut rrule = None;
+                let mut dtstamp = None;
+                let mut rdates = vec![];
+                let mut exdates = vec![];
+                let mut organizer = None;
+                let mut attendees = vec![];
+                let mut extra = vec![];
 
                 for prop in component.properties {
                     match prop.name.as_str() {
-                        "UID" => uid = prop.value,
-                        "SUMMARY" => summary = prop.value,
-                        "DESCRIPTION" => description = prop.value,
-                        "LOCATION" => location = prop.value,
-                        "DTSTART" => dtstart = prop.value,
-                        "DTEND" => dtend = prop.value,
-                        "STATUS" => status = prop.value,
-                        _ => {}
+                        "UID" => uid = prop.value.as_deref().map(unescape_text),
+                        "SUMMARY" => summary = prop.value.as_deref().map(unescape_text),
+                        "DESCRIPTION" => description = prop.value.as_deref().map(unescape_text),
+                        "LOCATION" => location = prop.value.as_deref().map(unescape_text),
+                        "DTSTART" => dtstart = Some(prop),
+                        "DTEND" => dtend = Some(prop),
+                        "DURATION" => duration = prop.value,
+                        "STATUS" => status = Some(prop),
+                        "RECURRENCE-ID" => recurrence_id = Some(prop),
+                        "RRULE" => rrule = Some(prop),
+                        "RDATE" => rdates.push(prop),
+                        "EXDATE" => exdates.push(prop),
+                        "ORGANIZER" => organizer = Some(parse_organizer(prop)),
+                        "ATTENDEE" => attendees.push(parse_attendee(prop)),
+                        "DTSTAMP" => dtstamp = Some(prop),
+                        _ => extra.push(raw_property(prop)),
                     }
                 }
 
                 let uid = uid.ok_or_else(|| anyhow!("Missing UID in event"))?;
+
+                let (alarms, components) = parse_alarms(component.alarms);
                 let start = dtstart
-                    .as_deref()
+                    .as_ref()
                     .ok_or_else(|| anyhow!("Missing DTSTART in event {}", uid))
-                    .and_then(parse_datetime)?;
-                let end = dtend
+                    .and_then(|p| parse_event_time(p, &timezones))?;
+                let duration = duration
                     .as_deref()
-                    .ok_or_else(|| anyhow!("Missing DTEND in event {}", uid))
-                    .and_then(parse_datetime)?;
+                    .map(EventDuration::from_str)
+                    .transpose()
+                    .with_context(|| format!("Invalid DURATION in event {}", uid))?;
+
+                // without DTEND and DURATION a date lasts one day, a date-time has no length
+                let end = match (&dtend, &duration) {
+                    (Some(p), _) => parse_event_time(p, &timezones)?,
+                    (None, Some(d)) => start
+                        .plus(d)
+                        .ok_or_else(|| anyhow!("DURATION out of range in event {}", uid))?,
+                    (None, None) if start.is_date() => start
+                        .plus(&EventDuration {
+                            days: 1,
+                            time: Duration::zero(),
+                        })
+                        .unwrap_or_else(|| start.clone()),
+                    (None, None) => start.clone(),
+                };
+
+                // a rule that can't be expanded is kept as it is, the event shows once
+                let mut rule = None;
+                if let Some(prop) = rrule {
+                    match RecurrenceRule::from_str(prop.value.as_deref().unwrap_or_default()) {
+                        Ok(r) => rule = Some(r),
+                        Err(_) => extra.push(raw_property(prop)),
+                    }
+                }
+                let recurrence = Recurrence {
+                    rule,
+                    rdates: parse_event_time_list(&rdates, &timezones)?,
+                    exdates: parse_event_time_list(&exdates, &timezones)?,
+                };
+
+                // a status we don't know is kept as it is
+                let status = match status {
+                    Some(prop) => {
+                        match EventStatus::from_str(prop.value.as_deref().unwrap_or_default())? {
+                            EventStatus::Unknown => {
+                                extra.push(raw_property(prop));
+                                EventStatus::Unknown
+                            }
+                            status => status,
+                        }
+                    }
+                    None => EventStatus::Unknown,
+                };
 
                 let event = CalendarEvent {
                     uid,
@@ -61,7 +203,20 @@ impl<B: BufRead> IcalParser<B> for GcalParser {
                     location,
                     start,
                     end,
-                    status: EventStatus::Unknown, // todo
+                    duration: duration.filter(|_| dtend.is_none()),
+                    status,
+                    recurrence: (!recurrence.is_empty()).then_some(recurrence),
+                    recurrence_id: recurrence_id
+                        .as_ref()
+                        .map(|p| parse_event_time(p, &timezones))
+                        .transpose()?,
+                    overrides: vec![],
+                    dtstamp: dtstamp.and_then(|p| parse_dtstamp(&p, &timezones)),
+                    organizer,
+                    attendees,
+                    extra,
+                    alarms,
+                    components,
                 };
 
                 events.push(event);
@@ -69,24 +224,408 @@ impl<B: BufRead> IcalParser<B> for GcalParser {
         }
 
         Ok(Calendar {
-            name: String::from_str("todo")?,
-            events,
+            tasks,
+            journals,
+            timezones: all_timezones,
+            extra,
+            components,
+            ..Calendar::from_events(name.unwrap_or_default(), events)
         })
     }
 }
 
-fn parse_datetime(value: &str) -> Result<DateTime<Utc>> {
-    // Parse both
+fn parse_organizer(prop: Property) -> Organizer {
+    let mut organizer = Organizer {
+        address: prop.value.unwrap_or_default(),
+        name: None,
+        params: vec![],
+    };
+
+    for (k, v) in prop.params.unwrap_or_default() {
+        match k.as_str() {
+            "CN" => organizer.name = v.into_iter().next(),
+            _ => organizer.params.push((k, v)),
+        }
+    }
+
+    organizer
+}
+
+fn parse_attendee(prop: Property) -> Attendee {
+    let mut attendee = Attendee {
+        address: prop.value.unwrap_or_default(),
+        name: None,
+        role: Role::default(),
+        partstat: PartStat::default(),
+        rsvp: false,
+        params: vec![],
+    };
+
+    for (k, v) in prop.params.unwrap_or_default() {
+        let first = v.first().map(String::as_str).unwrap_or_default();
+
+        match k.as_str() {
+            "CN" => attendee.name = v.into_iter().next(),
+            "ROLE" => attendee.role = first.parse().unwrap_or_default(),
+            "PARTSTAT" => attendee.partstat = first.parse().unwrap_or_default(),
+            "RSVP" => attendee.rsvp = first.eq_ignore_ascii_case("TRUE"),
+            _ => attendee.params.push((k, v)),
+        }
+    }
+
+    attendee
+}
+
+fn parse_task(todo: &IcalTodo, timezones: &Timezones) -> Result<Task> {
+    let mut task = Task {
+        uid: String::new(),
+        summary: None,
+        description: None,
+        due: None,
+        priority: 0,
+        status: TaskStatus::Unknown,
+        percent_complete: None,
+        completed: None,
+        parent: None,
+        parent_params: vec![],
+        dtstamp: None,
+        extra: vec![],
+        alarms: vec![],
+        components: vec![],
+    };
+    let mut uid = None;
+
+    for prop in &todo.properties {
+        let value = prop.value.as_deref().unwrap_or_default();
+
+        match prop.name.as_str() {
+            "UID" => uid = Some(unescape_text(value)),
+            "SUMMARY" => task.summary = Some(unescape_text(value)),
+            "DESCRIPTION" => task.description = Some(unescape_text(value)),
+            "DUE" => task.due = Some(parse_event_time(prop, timezones)?),
+            "PRIORITY" => task.priority = value.parse()?,
+            // a status we don't know is kept as it is
+            "STATUS" => match TaskStatus::from_str(value)? {
+                TaskStatus::Unknown => task.extra.push(raw_property(prop.clone())),
+                status => task.status = status,
+            },
+            "PERCENT-COMPLETE" => task.percent_complete = Some(value.parse()?),
+            "COMPLETED" => {
+                task.completed = Some(
+                    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+                        .map_err(|_| anyhow!("Invalid COMPLETED: {}", value))?
+                        .and_utc(),
+                )
+            }
+            // only the parent is tracked, siblings and children stay as they are
+            "RELATED-TO"
+                if task.parent.is_none()
+                    && param(prop, "RELTYPE").is_none_or(|t| t == "PARENT") =>
+            {
+                task.parent = Some(unescape_text(value));
+                task.parent_params = prop.params.clone().unwrap_or_default();
+            }
+            "DTSTAMP" => task.dtstamp = parse_dtstamp(prop, timezones),
+            _ => task.extra.push(raw_property(prop.clone())),
+        }
+    }
+
+    task.uid = uid.ok_or_else(|| anyhow!("Missing UID in task"))?;
+    (task.alarms, task.components) = parse_alarms(todo.alarms.clone());
+
+    Ok(task)
+}
+
+fn parse_journal(journal: &IcalJournal, timezones: &Timezones) -> Result<JournalEntry> {
+    let mut uid = None;
+    let mut start = None;
+    let mut summary = None;
+    let mut description = None;
+    let mut dtstamp = None;
+    let mut extra = vec![];
+
+    for prop in &journal.properties {
+        let value = prop.value.as_deref().unwrap_or_default();
+
+        match prop.name.as_str() {
+            "UID" => uid = Some(unescape_text(value)),
+            "DTSTART" => start = Some(parse_event_time(prop, timezones)?),
+            "SUMMARY" => summary = Some(unescape_text(value)),
+            "DESCRIPTION" if description.is_none() => description = Some(unescape_text(value)),
+            "DTSTAMP" => dtstamp = parse_dtstamp(prop, timezones),
+            _ => extra.push(raw_property(prop.clone())),
+        }
+    }
+
+    Ok(JournalEntry {
+        uid: uid.ok_or_else(|| anyhow!("Missing UID in journal"))?,
+        start: start.ok_or_else(|| anyhow!("Missing DTSTART in journal"))?,
+        summary,
+        description: description.unwrap_or_default(),
+        dtstamp,
+        extra,
+    })
+}
+
+// not a UTC time is against RFC 5545, regenerated on write then
+fn parse_dtstamp(prop: &Property, timezones: &Timezones) -> Option<DateTime<Utc>> {
+    match parse_event_time(prop, timezones) {
+        Ok(EventTime::Utc(dt)) => Some(dt),
+        _ => None,
+    }
+}
+
+// alarms we can't make sense of are kept as they are
+fn parse_alarms(alarms: Vec<IcalAlarm>) -> (Vec<Alarm>, Vec<RawComponent>) {
+    let mut parsed = vec![];
+    let mut components = vec![];
+
+    for alarm in alarms {
+        match parse_alarm(&alarm.properties) {
+            Ok(a) => parsed.push(a),
+            Err(_) => components.extend(alarm_components(vec![alarm])),
+        }
+    }
+
+    (parsed, components)
+}
+
+fn parse_alarm(properties: &[Property]) -> Result<Alarm> {
+    let mut action = None;
+    let mut trigger = None;
+    let mut description = None;
+    let mut repeat = 0;
+    let mut interval = None;
+    let mut extra = vec![];
+
+    for prop in properties {
+        let value = prop.value.as_deref().unwrap_or_default();
+
+        match prop.name.as_str() {
+            "ACTION" => action = Some(AlarmAction::from_str(value)?),
+            "TRIGGER" => {
+                trigger = Some(if param(prop, "VALUE") == Some("DATE-TIME") {
+                    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+                        .map(|dt| AlarmTrigger::Absolute(dt.and_utc()))
+                        .map_err(|_| anyhow!("Invalid TRIGGER: {}", value))?
+                } else {
+                    AlarmTrigger::Relative {
+                        offset: value.parse()?,
+                        from_end: param(prop, "RELATED") == Some("END"),
+                    }
+                })
+            }
+            "DESCRIPTION" => description = Some(unescape_text(value)),
+            "REPEAT" => repeat = value.parse()?,
+            "DURATION" => interval = Some(value.parse()?),
+            _ => extra.push(raw_property(prop.clone())),
+        }
+    }
+
+    Ok(Alarm {
+        action: action.ok_or_else(|| anyhow!("Missing ACTION in VALARM"))?,
+        trigger: trigger.ok_or_else(|| anyhow!("Missing TRIGGER in VALARM"))?,
+        description,
+        repeat,
+        interval,
+        extra,
+    })
+}
+
+fn raw_property(prop: Property) -> RawProperty {
+    RawProperty {
+        name: prop.name,
+        params: prop.params.unwrap_or_default(),
+        value: prop.value.unwrap_or_default(),
+    }
+}
+
+fn raw_component(
+    name: &str,
+    properties: Vec<Property>,
+    components: Vec<RawComponent>,
+) -> RawComponent {
+    RawComponent {
+        name: name.to_string(),
+        properties: properties.into_iter().map(raw_property).collect(),
+        components,
+    }
+}
+
+fn alarm_components(alarms: Vec<IcalAlarm>) -> Vec<RawComponent> {
+    alarms
+        .into_iter()
+        .map(|a| raw_component("VALARM", a.properties, vec![]))
+        .collect()
+}
+
+// TEXT value unescaping (RFC 5545 3.3.11), the ical crate keeps values as written
+fn unescape_text(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+fn param<'p>(prop: &'p Property, name: &str) -> Option<&'p str> {
+    prop.params
+        .as_ref()?
+        .iter()
+        .find(|(k, _)| k == name)
+        .and_then(|(_, v)| v.first())
+        .map(String::as_str)
+}
+
+// IANA names win over VTIMEZONE blocks of the file, unknown TZIDs are treated as floating
+fn resolve_zone(tzid: &str, timezones: &Timezones) -> Option<Zone> {
+    let tzid = tzid.trim_start_matches('/');
+
+    Tz::from_str(tzid)
+        .ok()
+        .map(Zone::Iana)
+        .or_else(|| timezones.get(tzid).cloned().map(Zone::Custom))
+}
+
+fn parse_event_time(prop: &Property, timezones: &Timezones) -> Result<EventTime> {
+    let value = prop
+        .value
+        .as_deref()
+        .ok_or_else(|| anyhow!("Missing value of {}", prop.name))?;
+
+    if param(prop, "VALUE") == Some("DATE") {
+        return parse_date(value);
+    }
+
+    parse_event_time_value(value, param(prop, "TZID"), timezones)
+}
+
+fn parse_date(value: &str) -> Result<EventTime> {
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .map(EventTime::Date)
+        .map_err(|_| anyhow!("Failed to parse date: {}", value))
+}
+
+fn parse_event_time_value(
+    value: &str,
+    tzid: Option<&str>,
+    timezones: &Timezones,
+) -> Result<EventTime> {
+    // Parse
     // - UTC: 20250705T130000Z
-    // - Local time: 20250705T150000
-    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
-        Ok(dt.with_timezone(&Utc))
-    } else if let Ok(dt) = DateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
-        Ok(dt.with_timezone(&Utc))
-    } else if let Ok(dt) = DateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
-        // No TZ info - assume UTC or local depending on your app
-        Ok(Utc.from_utc_datetime(&dt.naive_utc()))
+    // - Local time: 20250705T150000, floating or with TZID param
+    // - Date: 20250705
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        Ok(EventTime::Utc(dt.and_utc()))
+    } else if let Ok(dt) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        match tzid.and_then(|tzid| resolve_zone(tzid, timezones)) {
+            Some(zone) => Ok(EventTime::Zoned(dt, zone)),
+            None => Ok(EventTime::Floating(dt)),
+        }
+    } else if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        Ok(EventTime::Utc(dt.with_timezone(&Utc)))
+    } else if value.len() == 8 {
+        // VALUE=DATE is sometimes omitted
+        parse_date(value)
     } else {
         Err(anyhow!("Failed to parse datetime: {}", value))
     }
 }
+
+// RDATE/EXDATE values, possibly several per property, e.g. `20250705T130000Z,20250712T130000Z`
+// RDATE periods (`start/end` or `start/duration`) only keep their start
+fn parse_event_time_list(props: &[Property], timezones: &Timezones) -> Result<Vec<EventTime>> {
+    props
+        .iter()
+        .flat_map(|p| {
+            let tzid = param(p, "TZID");
+            p.value
+                .iter()
+                .flat_map(|v| v.split(','))
+                .map(move |v| (v, tzid))
+        })
+        .map(|(v, tzid)| parse_event_time_value(v.split('/').next().unwrap_or(v), tzid, timezones))
+        .collect()
+}
+
+fn parse_timezone(tz: &IcalTimeZone) -> Result<VTimezone> {
+    let tzid = tz
+        .properties
+        .iter()
+        .find(|p| p.name == "TZID")
+        .and_then(|p| p.value.clone())
+        .ok_or_else(|| anyhow!("Missing TZID in VTIMEZONE"))?;
+
+    let observances = tz
+        .transitions
+        .iter()
+        .map(|t| {
+            let mut start = None;
+            let mut offset_from = None;
+            let mut offset_to = None;
+            let mut rule = None;
+            let mut rdates = vec![];
+            let mut name = None;
+            let mut extra = vec![];
+
+            for prop in &t.properties {
+                let value = prop.value.as_deref().unwrap_or_default();
+
+                match prop.name.as_str() {
+                    "DTSTART" => {
+                        start = Some(NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")?)
+                    }
+                    "TZOFFSETFROM" => offset_from = Some(parse_utc_offset(value)?),
+                    "TZOFFSETTO" => offset_to = Some(parse_utc_offset(value)?),
+                    "RRULE" => rule = Some(RecurrenceRule::from_str(value)?),
+                    // periods and UTC times are not expected here, kept as they are
+                    "RDATE" => match value
+                        .split(',')
+                        .map(|v| NaiveDateTime::parse_from_str(v, "%Y%m%dT%H%M%S"))
+                        .collect::<Result<Vec<_>, _>>()
+                    {
+                        Ok(dates) => rdates.extend(dates),
+                        Err(_) => extra.push(raw_property(prop.clone())),
+                    },
+                    "TZNAME" => name = Some(value.to_string()),
+                    _ => extra.push(raw_property(prop.clone())),
+                }
+            }
+
+            Ok(Observance {
+                daylight: matches!(t.transition, IcalTimeZoneTransitionType::DAYLIGHT),
+                start: start.ok_or_else(|| anyhow!("Missing DTSTART"))?,
+                offset_from: offset_from.ok_or_else(|| anyhow!("Missing TZOFFSETFROM"))?,
+                offset_to: offset_to.ok_or_else(|| anyhow!("Missing TZOFFSETTO"))?,
+                rule,
+                rdates,
+                name,
+                extra,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("Invalid VTIMEZONE {}", tzid))?;
+
+    let mut vtz = VTimezone::new(tzid, observances);
+    vtz.extra = tz
+        .properties
+        .iter()
+        .filter(|p| p.name != "TZID")
+        .cloned()
+        .map(raw_property)
+        .collect();
+
+    Ok(vtz)
+}
@@ -2,11 +2,7 @@ use crate::{
     common::calendar::{Calendar, CalendarSource, IcalParser},
     providers::gcal::parser::GcalParser,
 };
-use std::{
-    fs::{self, File},
-    io::BufReader,
-    path::PathBuf,
-};
+use std::{fs::File, io::BufReader, path::PathBuf};
 
 pub(super) struct GcalSrc {
     ics_path: PathBuf,
@@ -23,6 +19,14 @@ impl CalendarSource for GcalSrc {
         let r = BufReader::new(File::open(&self.ics_path)?);
         let parser = GcalParser::new();
 
-        parser.parse(r)
+        let mut calendar = parser.parse(r)?;
+        // files without X-WR-CALNAME are named after the file
+        if calendar.name.is_empty()
+            && let Some(stem) = self.ics_path.file_stem()
+        {
+            calendar.name = stem.to_string_lossy().into_owned();
+        }
+
+        Ok(calendar)
     }
 }
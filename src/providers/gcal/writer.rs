@@ -0,0 +1,518 @@
+use chrono::{DateTime, Datelike, Utc};
+use chrono_tz::Tz;
+use std::{ops::RangeInclusive, sync::Arc};
+
+use crate::common::{
+    alarm::{Alarm, AlarmTrigger},
+    calendar::{
+        Calendar, CalendarEvent, CalendarWriter, PartStat, RawComponent, RawProperty, Role,
+    },
+    journal::JournalEntry,
+    task::Task,
+    time::{EventTime, VTimezone, Zone, format_utc_offset},
+};
+
+const PRODID: &str = "-//taskrs//taskrs//EN";
+
+// calendar properties written first, as read or these when the calendar has none
+const HEADER: [(&str, &str); 3] = [
+    ("VERSION", "2.0"),
+    ("PRODID", PRODID),
+    ("CALSCALE", "GREGORIAN"),
+];
+
+// lines are folded to this many octets, CRLF excluded (RFC 5545 3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+// generated VTIMEZONE blocks cover this many years after the export
+const TIMEZONE_YEARS: i32 = 10;
+
+pub(super) struct GcalWriter {}
+
+impl GcalWriter {
+    pub(super) fn new() -> GcalWriter {
+        Self {}
+    }
+
+    // one event with its modified instances, without the calendar around them
+    pub(super) fn write_event(&self, event: &CalendarEvent) -> String {
+        let mut out = Output::default();
+        let dtstamp = Utc::now();
+
+        write_event(&mut out, event, dtstamp);
+        for o in &event.overrides {
+            write_event(&mut out, o, dtstamp);
+        }
+
+        out.buf
+    }
+}
+
+impl CalendarWriter for GcalWriter {
+    fn write(&self, calendar: &Calendar) -> anyhow::Result<String> {
+        let mut out = Output::default();
+        let dtstamp = Utc::now();
+
+        out.line("BEGIN:VCALENDAR");
+        for (name, value) in HEADER {
+            match calendar.extra.iter().find(|p| p.name == name) {
+                Some(prop) => out.raw(prop),
+                None => out.prop(name, &[], value),
+            }
+        }
+        if !calendar.name.is_empty() {
+            out.prop("X-WR-CALNAME", &[], &escape_text(&calendar.name));
+        }
+        for prop in &calendar.extra {
+            if !HEADER.iter().any(|(name, _)| prop.name == *name) {
+                out.raw(prop);
+            }
+        }
+
+        for tz in timezones(calendar, dtstamp.year() + TIMEZONE_YEARS) {
+            write_timezone(&mut out, &tz);
+        }
+
+        for event in &calendar.events {
+            write_event(&mut out, event, dtstamp);
+            for o in &event.overrides {
+                write_event(&mut out, o, dtstamp);
+            }
+        }
+
+        for task in &calendar.tasks {
+            write_task(&mut out, task, dtstamp);
+        }
+
+        for entry in &calendar.journals {
+            write_journal(&mut out, entry, dtstamp);
+        }
+
+        for component in &calendar.components {
+            out.component(component);
+        }
+
+        out.line("END:VCALENDAR");
+
+        Ok(out.buf)
+    }
+}
+
+#[derive(Default)]
+struct Output {
+    buf: String,
+}
+
+impl Output {
+    fn line(&mut self, line: &str) {
+        fold(&mut self.buf, line);
+    }
+
+    fn prop(&mut self, name: &str, params: &[(&str, &str)], value: &str) {
+        let mut line = String::from(name);
+        for (k, v) in params {
+            push_param(&mut line, k, &[v]);
+        }
+        line.push(':');
+        line.push_str(value);
+
+        self.line(&line);
+    }
+
+    fn raw(&mut self, prop: &RawProperty) {
+        let mut line = prop.name.clone();
+        for (k, values) in &prop.params {
+            push_param(&mut line, k, values);
+        }
+        line.push(':');
+        line.push_str(&prop.value);
+
+        self.line(&line);
+    }
+
+    fn component(&mut self, component: &RawComponent) {
+        self.line(&format!("BEGIN:{}", component.name));
+        for prop in &component.properties {
+            self.raw(prop);
+        }
+        for sub in &component.components {
+            self.component(sub);
+        }
+        self.line(&format!("END:{}", component.name));
+    }
+
+    fn time(&mut self, name: &str, time: &EventTime) {
+        match time {
+            EventTime::Date(d) => {
+                self.prop(name, &[("VALUE", "DATE")], &d.format("%Y%m%d").to_string())
+            }
+            EventTime::Floating(dt) => {
+                self.prop(name, &[], &dt.format("%Y%m%dT%H%M%S").to_string())
+            }
+            EventTime::Utc(dt) => self.prop(name, &[], &dt.format("%Y%m%dT%H%M%SZ").to_string()),
+            EventTime::Zoned(dt, zone) => self.prop(
+                name,
+                &[("TZID", zone.name())],
+                &dt.format("%Y%m%dT%H%M%S").to_string(),
+            ),
+        }
+    }
+}
+
+fn param(name: &str, value: &str) -> (String, Vec<String>) {
+    (name.to_string(), vec![value.to_string()])
+}
+
+// `;NAME=v1,v2`, values with separators in them are quoted, DQUOTE can't be
+// escaped in parameters (RFC 5545 3.2) so it becomes a single quote
+fn push_param<S: AsRef<str>>(line: &mut String, name: &str, values: &[S]) {
+    line.push(';');
+    line.push_str(name);
+    line.push('=');
+
+    for (i, v) in values.iter().enumerate() {
+        let v = v.as_ref().replace('"', "'");
+        if i > 0 {
+            line.push(',');
+        }
+        if v.contains([':', ';', ',']) {
+            line.push('"');
+            line.push_str(&v);
+            line.push('"');
+        } else {
+            line.push_str(&v);
+        }
+    }
+}
+
+fn write_event(out: &mut Output, event: &CalendarEvent, dtstamp: DateTime<Utc>) {
+    out.line("BEGIN:VEVENT");
+    out.prop("UID", &[], &escape_text(&event.uid));
+    out.time("DTSTAMP", &EventTime::Utc(event.dtstamp.unwrap_or(dtstamp)));
+    out.time("DTSTART", &event.start);
+    match &event.duration {
+        Some(duration) => out.prop("DURATION", &[], &duration.to_string()),
+        None => out.time("DTEND", &event.end),
+    }
+
+    if let Some(recurrence_id) = &event.recurrence_id {
+        out.time("RECURRENCE-ID", recurrence_id);
+    }
+
+    if let Some(recurrence) = &event.recurrence {
+        if let Some(rule) = &recurrence.rule {
+            out.prop("RRULE", &[], &rule.to_string());
+        }
+        for rdate in &recurrence.rdates {
+            out.time("RDATE", rdate);
+        }
+        for exdate in &recurrence.exdates {
+            out.time("EXDATE", exdate);
+        }
+    }
+
+    let text = [
+        ("SUMMARY", &event.summary),
+        ("DESCRIPTION", &event.description),
+        ("LOCATION", &event.location),
+    ];
+    for (name, value) in text {
+        if let Some(value) = value {
+            out.prop(name, &[], &escape_text(value));
+        }
+    }
+
+    if let Some(status) = event.status.as_ical() {
+        out.prop("STATUS", &[], status);
+    }
+
+    if let Some(organizer) = &event.organizer {
+        let mut params = vec![];
+        if let Some(name) = &organizer.name {
+            params.push(param("CN", name));
+        }
+        params.extend(organizer.params.iter().cloned());
+
+        out.raw(&RawProperty {
+            name: "ORGANIZER".to_string(),
+            params,
+            value: organizer.address.clone(),
+        });
+    }
+
+    for attendee in &event.attendees {
+        let mut params = vec![];
+        if let Some(name) = &attendee.name {
+            params.push(param("CN", name));
+        }
+        if attendee.role != Role::default() {
+            params.push(param("ROLE", attendee.role.as_ical()));
+        }
+        if attendee.partstat != PartStat::default() {
+            params.push(param("PARTSTAT", attendee.partstat.as_ical()));
+        }
+        if attendee.rsvp {
+            params.push(param("RSVP", "TRUE"));
+        }
+        params.extend(attendee.params.iter().cloned());
+
+        out.raw(&RawProperty {
+            name: "ATTENDEE".to_string(),
+            params,
+            value: attendee.address.clone(),
+        });
+    }
+
+    for prop in &event.extra {
+        out.raw(prop);
+    }
+    for alarm in &event.alarms {
+        write_alarm(out, alarm);
+    }
+    for component in &event.components {
+        out.component(component);
+    }
+
+    out.line("END:VEVENT");
+}
+
+fn write_task(out: &mut Output, task: &Task, dtstamp: DateTime<Utc>) {
+    out.line("BEGIN:VTODO");
+    out.prop("UID", &[], &escape_text(&task.uid));
+    out.time("DTSTAMP", &EventTime::Utc(task.dtstamp.unwrap_or(dtstamp)));
+    if let Some(due) = &task.due {
+        out.time("DUE", due);
+    }
+
+    let text = [
+        ("SUMMARY", &task.summary),
+        ("DESCRIPTION", &task.description),
+    ];
+    for (name, value) in text {
+        if let Some(value) = value {
+            out.prop(name, &[], &escape_text(value));
+        }
+    }
+
+    if let Some(status) = task.status.as_ical() {
+        out.prop("STATUS", &[], status);
+    }
+    if task.priority > 0 {
+        out.prop("PRIORITY", &[], &task.priority.to_string());
+    }
+    if let Some(percent) = task.percent_complete {
+        out.prop("PERCENT-COMPLETE", &[], &percent.to_string());
+    }
+    if let Some(completed) = task.completed {
+        out.time("COMPLETED", &EventTime::Utc(completed));
+    }
+    if let Some(parent) = &task.parent {
+        out.raw(&RawProperty {
+            name: "RELATED-TO".to_string(),
+            params: task.parent_params.clone(),
+            value: escape_text(parent),
+        });
+    }
+
+    for prop in &task.extra {
+        out.raw(prop);
+    }
+    for alarm in &task.alarms {
+        write_alarm(out, alarm);
+    }
+    for component in &task.components {
+        out.component(component);
+    }
+
+    out.line("END:VTODO");
+}
+
+fn write_journal(out: &mut Output, entry: &JournalEntry, dtstamp: DateTime<Utc>) {
+    out.line("BEGIN:VJOURNAL");
+    out.prop("UID", &[], &escape_text(&entry.uid));
+    out.time("DTSTAMP", &EventTime::Utc(entry.dtstamp.unwrap_or(dtstamp)));
+    out.time("DTSTART", &entry.start);
+    if let Some(summary) = &entry.summary {
+        out.prop("SUMMARY", &[], &escape_text(summary));
+    }
+    if !entry.description.is_empty() {
+        out.prop("DESCRIPTION", &[], &escape_text(&entry.description));
+    }
+    for prop in &entry.extra {
+        out.raw(prop);
+    }
+
+    out.line("END:VJOURNAL");
+}
+
+fn write_alarm(out: &mut Output, alarm: &Alarm) {
+    out.line("BEGIN:VALARM");
+    out.prop("ACTION", &[], alarm.action.as_ical());
+
+    match alarm.trigger {
+        AlarmTrigger::Relative { offset, from_end } => {
+            let params: &[(&str, &str)] = if from_end { &[("RELATED", "END")] } else { &[] };
+            out.prop("TRIGGER", params, &offset.to_string());
+        }
+        AlarmTrigger::Absolute(at) => out.prop(
+            "TRIGGER",
+            &[("VALUE", "DATE-TIME")],
+            &at.format("%Y%m%dT%H%M%SZ").to_string(),
+        ),
+    }
+
+    if let Some(description) = &alarm.description {
+        out.prop("DESCRIPTION", &[], &escape_text(description));
+    }
+    if alarm.repeat > 0 {
+        out.prop("REPEAT", &[], &alarm.repeat.to_string());
+    }
+    if let Some(interval) = &alarm.interval {
+        out.prop("DURATION", &[], &interval.to_string());
+    }
+    for prop in &alarm.extra {
+        out.raw(prop);
+    }
+
+    out.line("END:VALARM");
+}
+
+// VTIMEZONE blocks for every zone in use: custom ones as they were read, IANA
+// ones generated for the years they are used in, up to `horizon` at least, so
+// clients without the IANA database still get the offsets right
+fn timezones(calendar: &Calendar, horizon: i32) -> Vec<Arc<VTimezone>> {
+    let mut timezones = calendar.timezones.clone();
+    let mut iana: Vec<(Tz, RangeInclusive<i32>)> = vec![];
+
+    let events = calendar
+        .events
+        .iter()
+        .flat_map(|e| std::iter::once(e).chain(&e.overrides));
+
+    let mut times: Vec<&EventTime> = vec![];
+    for event in events {
+        let recurrence = event
+            .recurrence
+            .iter()
+            .flat_map(|r| r.rdates.iter().chain(&r.exdates));
+        times.extend(
+            [&event.start, &event.end]
+                .into_iter()
+                .chain(&event.recurrence_id)
+                .chain(recurrence),
+        );
+    }
+    times.extend(calendar.tasks.iter().filter_map(|t| t.due.as_ref()));
+    times.extend(calendar.journals.iter().map(|j| &j.start));
+
+    for time in times {
+        match time {
+            EventTime::Zoned(_, Zone::Custom(tz))
+                if !timezones.iter().any(|t| t.tzid == tz.tzid) =>
+            {
+                timezones.push(tz.clone());
+            }
+            EventTime::Zoned(dt, Zone::Iana(tz)) => {
+                let year = dt.year();
+                match iana.iter_mut().find(|(t, _)| t == tz) {
+                    Some((_, years)) => {
+                        *years = year.min(*years.start())..=year.max(*years.end());
+                    }
+                    None => iana.push((*tz, year..=year.max(horizon))),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (tz, years) in iana {
+        if !timezones.iter().any(|t| t.tzid == tz.name()) {
+            timezones.push(Arc::new(VTimezone::from_iana(tz, years)));
+        }
+    }
+
+    timezones
+}
+
+fn write_timezone(out: &mut Output, tz: &VTimezone) {
+    out.line("BEGIN:VTIMEZONE");
+    out.prop("TZID", &[], &tz.tzid);
+    for prop in &tz.extra {
+        out.raw(prop);
+    }
+
+    for o in &tz.observances {
+        let kind = if o.daylight { "DAYLIGHT" } else { "STANDARD" };
+
+        out.line(&format!("BEGIN:{}", kind));
+        out.prop("DTSTART", &[], &o.start.format("%Y%m%dT%H%M%S").to_string());
+        out.prop("TZOFFSETFROM", &[], &format_utc_offset(o.offset_from));
+        out.prop("TZOFFSETTO", &[], &format_utc_offset(o.offset_to));
+        if let Some(rule) = &o.rule {
+            out.prop("RRULE", &[], &rule.to_string());
+        }
+        if !o.rdates.is_empty() {
+            let rdates: Vec<String> = o
+                .rdates
+                .iter()
+                .map(|d| d.format("%Y%m%dT%H%M%S").to_string())
+                .collect();
+            out.prop("RDATE", &[], &rdates.join(","));
+        }
+        if let Some(name) = &o.name {
+            out.prop("TZNAME", &[], name);
+        }
+        for prop in &o.extra {
+            out.raw(prop);
+        }
+        out.line(&format!("END:{}", kind));
+    }
+
+    out.line("END:VTIMEZONE");
+}
+
+// TEXT value escaping (RFC 5545 3.3.11)
+fn escape_text(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.replace("\r\n", "\n").chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Appends `line` folded into chunks of at most 75 octets, never splitting a UTF-8 character.
+// Readers trim line ends, so chunks don't end with whitespace when it can be avoided.
+fn fold(buf: &mut String, line: &str) {
+    let mut rest = line;
+    // continuation lines start with a space
+    let mut limit = MAX_LINE_OCTETS;
+
+    while rest.len() > limit {
+        let mut at = limit;
+        while !rest.is_char_boundary(at) {
+            at -= 1;
+        }
+
+        let trimmed = rest[..at].trim_end_matches([' ', '\t']).len();
+        if trimmed > 0 {
+            at = trimmed;
+        }
+
+        buf.push_str(&rest[..at]);
+        buf.push_str("\r\n ");
+        rest = &rest[at..];
+        limit = MAX_LINE_OCTETS - 1;
+    }
+
+    buf.push_str(rest);
+    buf.push_str("\r\n");
+}
@@ -1,14 +1,140 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, path::PathBuf, rc::Rc, sync::mpsc::Sender};
 
-use icalendar::Calendar;
+use chrono::{NaiveDate, NaiveTime, Utc};
+
+use crate::{
+    common::{calendar::CalendarEvent, index::CalendarIndex, reminder::ReminderScheduler},
+    config::Config,
+    message::AppMessage,
+    ui::common::utils::today,
+};
 
 pub struct State {
-    pub calendar: Option<Calendar>,
-    pub tmp: Option<String>,
+    pub config: Config,
+    pub calendar: Option<CalendarIndex>,
+    /// number of times `calendar` was loaded, views keeping events read from it compare
+    /// it to tell when it was replaced
+    pub calendar_loads: u64,
+    /// last error while loading calendar, shown in ui
+    pub load_error: Option<String>,
+    /// file being loaded in background
+    pub loading: Option<PathBuf>,
+    /// event under the cursor of the current view, shown by the details popup
+    pub selected_event: Option<CalendarEvent>,
+    /// day under the cursor, shared by all views so switching keeps it; the journal shows
+    /// its entry
+    pub selected_date: NaiveDate,
+    /// time of day under the cursor of the daily and weekly views
+    pub selected_time: NaiveTime,
+    pub reminders: ReminderScheduler,
+}
+
+impl State {
+    pub fn new(config: Config) -> Self {
+        Self {
+            selected_date: today(&config.timezone),
+            selected_time: Utc::now().with_timezone(&config.timezone).time(),
+            config,
+            calendar: None,
+            calendar_loads: 0,
+            load_error: None,
+            loading: None,
+            selected_event: None,
+            reminders: ReminderScheduler::new(Utc::now()),
+        }
+    }
+
+    pub fn set_calendar(&mut self, calendar: CalendarIndex) {
+        self.calendar = Some(calendar);
+        self.calendar_loads += 1;
+    }
 }
 
 #[derive(Clone)]
 /// global app state
 pub struct AppState {
     pub state: Rc<RefCell<State>>,
+    /// channel into the app loop, for work done outside of the ui thread
+    pub messages: Sender<AppMessage>,
+}
+
+impl AppState {
+    pub fn new(state: State, messages: Sender<AppMessage>) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(state)),
+            messages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use chrono_tz::Tz;
+    use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::ui::{
+        common::view::View, daily::DailyView, monthly::MonthlyView, weekly::WeeklyView,
+    };
+
+    fn press(view: &mut impl View, keys: &str) {
+        for c in keys.chars() {
+            let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            view.handle_event(&Event::Key(key)).unwrap();
+            view.update();
+        }
+    }
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 10, d).unwrap()
+    }
+
+    #[test]
+    fn selection_is_carried_between_views() {
+        let mut state = State::new(Config {
+            timezone: Tz::UTC,
+            ..Config::default()
+        });
+        state.selected_date = date(20);
+        state.selected_time = NaiveTime::from_hms_opt(9, 10, 0).unwrap();
+        let state = AppState::new(state, channel().0);
+        let selected = || {
+            let state = state.state.borrow();
+            (state.selected_date, state.selected_time)
+        };
+
+        // the cursor shown on the selected day, a week down and a day right
+        let mut monthly = MonthlyView::new(state.clone());
+        monthly.update();
+        press(&mut monthly, "sjl");
+        assert_eq!(selected().0, date(28));
+
+        // the week opens on that day, the time row is the one under 09:10
+        let mut weekly = WeeklyView::new(state.clone());
+        weekly.update();
+        assert_eq!(
+            selected(),
+            (date(28), NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+        );
+        press(&mut weekly, "lj");
+        assert_eq!(
+            selected(),
+            (date(29), NaiveTime::from_hms_opt(9, 30, 0).unwrap())
+        );
+
+        let mut daily = DailyView::new(state.clone());
+        daily.update();
+        assert_eq!(
+            selected(),
+            (date(29), NaiveTime::from_hms_opt(9, 30, 0).unwrap())
+        );
+
+        // the month follows what the other views selected on its next update
+        monthly.update();
+        assert_eq!(selected().0, date(29));
+        press(&mut monthly, "l");
+        assert_eq!(selected().0, date(30));
+    }
 }
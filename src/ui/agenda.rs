@@ -0,0 +1,441 @@
+use std::cell::Cell;
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use anyhow::Result;
+
+use crate::{
+    common::calendar::CalendarEvent,
+    state::AppState,
+    ui::common::{
+        focusable::Focusable,
+        utils::{event_color, events_between, status_style, today},
+        view::{FocusableView, View, ViewKind},
+    },
+};
+
+// days loaded at once when scrolling past the loaded ones
+const CHUNK_DAYS: u64 = 30;
+// chunks without events skipped in one key press before giving up
+const MAX_EMPTY_CHUNKS: usize = 12;
+
+// Events in order of start under date headers, starting on the selected day; more days
+// are loaded when scrolling past the first or last event
+pub struct AgendaView {
+    state: AppState,
+    focused: bool,
+
+    // loaded days, inclusive
+    first: NaiveDate,
+    last: NaiveDate,
+    // events with the day they are listed under
+    events: Vec<(NaiveDate, CalendarEvent, Color)>,
+    selected: usize,
+    // selected day as this view last saw it, when it differs another view moved it
+    last_date: NaiveDate,
+    // `State::calendar_loads` when the events were read, they are read again when the
+    // calendar is loaded or replaced
+    loads: u64,
+
+    // first visible line, set while rendering
+    scroll: Cell<usize>,
+}
+
+impl AgendaView {
+    pub fn new(state: AppState) -> Self {
+        let selected = state.state.borrow().selected_date;
+
+        let mut view = Self {
+            state,
+            focused: false,
+            first: selected,
+            last: selected,
+            events: vec![],
+            selected: 0,
+            last_date: selected,
+            loads: 0,
+            scroll: Cell::new(0),
+        };
+        view.show_date(selected);
+
+        view
+    }
+
+    // reads events of loaded days, the selected event stays selected
+    fn load(&mut self) {
+        let key = |e: &CalendarEvent, tz| (e.uid.clone(), e.start_utc(tz));
+
+        let state = self.state.state.borrow();
+        let tz = &state.config.timezone;
+        let selected = self.events.get(self.selected).map(|(_, e, _)| key(e, tz));
+        self.loads = state.calendar_loads;
+
+        self.events = match &state.calendar {
+            Some(index) => events_between(index, self.first, self.last, &state.config)
+                .into_iter()
+                .map(|e| {
+                    // events started before the loaded days are listed on the first one
+                    let day = e.days(tz).0.max(self.first);
+                    let color = event_color(&e, index.calendar());
+                    (day, e, color)
+                })
+                .collect(),
+            None => vec![],
+        };
+        // events come sorted by start, grouping may only move earlier ones later
+        self.events
+            .sort_by_key(|(day, e, _)| (*day, e.start_utc(tz)));
+
+        self.selected = selected
+            .and_then(|k| self.events.iter().position(|(_, e, _)| key(e, tz) == k))
+            .unwrap_or(self.selected)
+            .min(self.events.len().saturating_sub(1));
+    }
+
+    // loads chunks of days forward or backward until one has events, false if none had
+    fn extend(&mut self, forward: bool) -> bool {
+        let loaded = self.events.len();
+
+        for _ in 0..MAX_EMPTY_CHUNKS {
+            let extended = if forward {
+                self.last.checked_add_days(Days::new(CHUNK_DAYS))
+            } else {
+                self.first.checked_sub_days(Days::new(CHUNK_DAYS))
+            };
+            let Some(day) = extended else {
+                return false;
+            };
+
+            if forward {
+                self.last = day;
+            } else {
+                self.first = day;
+            }
+            self.load();
+
+            if self.events.len() > loaded {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn move_down(&mut self) {
+        let had_events = !self.events.is_empty();
+        if self.selected + 1 >= self.events.len() && !self.extend(true) {
+            return;
+        }
+
+        if had_events {
+            self.selected += 1;
+        }
+    }
+
+    fn move_up(&mut self) {
+        let had_events = !self.events.is_empty();
+        if self.selected == 0 && !self.extend(false) {
+            return;
+        }
+
+        // the latest of the earlier events, nothing was selected before
+        self.selected = if had_events {
+            self.selected.saturating_sub(1)
+        } else {
+            self.events.len().saturating_sub(1)
+        };
+    }
+
+    // days from given one on
+    fn show_date(&mut self, date: NaiveDate) {
+        self.first = date;
+        self.last = date + Days::new(CHUNK_DAYS - 1);
+        self.events.clear();
+        self.selected = 0;
+        self.scroll.set(0);
+        self.load();
+    }
+
+    // date headers and event lines, with the index of the selected event's line
+    fn lines(&self) -> (Vec<Line<'static>>, usize) {
+        let state = self.state.state.borrow();
+        let tz = &state.config.timezone;
+        let now = today(tz);
+
+        let mut lines = vec![];
+        let mut selected_line = 0;
+        let mut day = None;
+
+        for (i, (event_day, event, color)) in self.events.iter().enumerate() {
+            if day != Some(*event_day) {
+                day = Some(*event_day);
+                if !lines.is_empty() {
+                    lines.push(Line::default());
+                }
+                lines.push(date_header(*event_day, now));
+            }
+
+            if i == self.selected {
+                selected_line = lines.len();
+            }
+
+            let line = event_line(event, *color, tz, Utc::now());
+            lines.push(if self.focused && i == self.selected {
+                line.reversed()
+            } else {
+                line
+            });
+        }
+
+        (lines, selected_line)
+    }
+}
+
+fn date_header(day: NaiveDate, today: NaiveDate) -> Line<'static> {
+    let text = day.format("%A %d %B %Y").to_string();
+
+    if day == today {
+        Line::from(vec![
+            text.fg(Color::Green).bold(),
+            Span::styled("  today", Style::new().fg(Color::Green)),
+        ])
+    } else {
+        Line::from(text.fg(Color::Blue).bold())
+    }
+}
+
+// `  10:00  1h 30m  ● Summary  @ Location`, past events dimmed
+fn event_line(
+    event: &CalendarEvent,
+    color: Color,
+    tz: &chrono_tz::Tz,
+    now: DateTime<Utc>,
+) -> Line<'static> {
+    let time = if event.is_all_day() {
+        "all day".to_string()
+    } else {
+        event.start.in_zone(tz).format("%H:%M").to_string()
+    };
+    let duration = format_duration(event.end_utc(tz) - event.start_utc(tz));
+
+    let mut spans = vec![
+        Span::raw(format!("  {time:<7}  {duration:>7}  ")),
+        Span::styled("● ", Style::new().fg(color)),
+        Span::styled(
+            event.summary.clone().unwrap_or_default(),
+            status_style(&event.status),
+        ),
+    ];
+    if let Some(location) = &event.location {
+        spans.push(Span::styled(format!("  @ {location}"), Style::new().dim()));
+    }
+
+    let line = Line::from(spans);
+    if event.end_utc(tz) <= now {
+        line.dim()
+    } else {
+        line
+    }
+}
+
+// `45m`, `1h 30m`, `2d`
+fn format_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+
+    let parts: Vec<String> = [(days, "d"), (hours, "h"), (minutes, "m")]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect();
+
+    if parts.is_empty() {
+        "0m".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+impl View for AgendaView {
+    fn kind(&self) -> Option<ViewKind> {
+        Some(ViewKind::Agenda)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from("AGENDA".bold());
+
+        let block = Block::new().title(title.centered());
+        let mut inner_area = block.inner(area);
+
+        // little offset
+        inner_area.y += 1;
+        inner_area.height = inner_area.height.saturating_sub(1);
+
+        block.render(area, buf);
+
+        let (lines, selected_line) = self.lines();
+        if lines.is_empty() {
+            Paragraph::new(Line::from(
+                "No events in the next days, j to look further".dim(),
+            ))
+            .render(inner_area, buf);
+            return;
+        }
+
+        // scrolled just enough for the selected event and its date header to be visible
+        let height = inner_area.height as usize;
+        let top = selected_line.saturating_sub(1);
+        let scroll = self
+            .scroll
+            .get()
+            .min(top)
+            .max((selected_line + 1).saturating_sub(height));
+        self.scroll.set(scroll);
+
+        Paragraph::new(lines)
+            .scroll((scroll as u16, 0))
+            .render(inner_area, buf);
+    }
+
+    fn handle_event(&mut self, e: &Event) -> Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            match key_ev.code {
+                KeyCode::Char('j') | KeyCode::Down => self.move_down(),
+                KeyCode::Char('k') | KeyCode::Up => self.move_up(),
+                KeyCode::Char('t') => {
+                    let now = today(&self.state.state.borrow().config.timezone);
+                    self.show_date(now);
+                }
+                _ => return Ok(()),
+            }
+
+            // moving selects the day of the event, while following another view its day stays
+            let mut state = self.state.state.borrow_mut();
+            if let Some((day, _, _)) = self.events.get(self.selected) {
+                state.selected_date = *day;
+            }
+            self.last_date = state.selected_date;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) {
+        let date = self.state.state.borrow().selected_date;
+        let loads = self.state.state.borrow().calendar_loads;
+        if date != self.last_date {
+            self.show_date(date);
+        } else if loads != self.loads {
+            self.load();
+        }
+
+        self.state.state.borrow_mut().selected_event =
+            self.events.get(self.selected).map(|(_, e, _)| e.clone());
+        self.last_date = date;
+    }
+}
+
+impl Focusable for AgendaView {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = !self.focused
+    }
+}
+
+impl FocusableView for AgendaView {
+    fn handle_event_if_focused(&mut self, e: &Event) -> Result<()> {
+        if self.focused {
+            self.handle_event(e)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use chrono_tz::Tz;
+
+    use super::*;
+    use crate::{
+        common::index::CalendarIndex, config::Config, providers::gcal::parse_ics, state::State,
+    };
+
+    fn index(summary: &str) -> CalendarIndex {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:meeting\r
+DTSTART:20251021T100000Z\r
+DTEND:20251021T110000Z\r
+SUMMARY:{summary}\r
+END:VEVENT\r
+END:VCALENDAR\r
+"
+        );
+        CalendarIndex::new(parse_ics(ics.as_bytes()).unwrap(), Tz::UTC)
+    }
+
+    fn summaries(view: &AgendaView) -> Vec<String> {
+        view.events
+            .iter()
+            .filter_map(|(_, e, _)| e.summary.clone())
+            .collect()
+    }
+
+    #[test]
+    fn events_are_read_again_when_the_calendar_is_replaced() {
+        let mut state = State::new(Config {
+            timezone: Tz::UTC,
+            ..Config::default()
+        });
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 10, 20).unwrap();
+        let state = AppState::new(state, channel().0);
+
+        // nothing loaded yet
+        let mut view = AgendaView::new(state.clone());
+        assert!(view.events.is_empty());
+
+        state.state.borrow_mut().set_calendar(index("Meeting"));
+        view.update();
+        assert_eq!(summaries(&view), ["Meeting"]);
+
+        // a calendar changed in place is not a new load, the events are kept as read
+        state.state.borrow_mut().calendar = Some(index("Changed"));
+        view.update();
+        assert_eq!(summaries(&view), ["Meeting"]);
+
+        state.state.borrow_mut().set_calendar(index("Moved"));
+        view.update();
+        assert_eq!(summaries(&view), ["Moved"]);
+        assert_eq!(
+            state
+                .state
+                .borrow()
+                .selected_event
+                .as_ref()
+                .and_then(|e| e.summary.as_deref()),
+            Some("Moved")
+        );
+    }
+}
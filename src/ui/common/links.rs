@@ -0,0 +1,122 @@
+// Handing links to the outside: the system clipboard and the default browser
+
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result, bail};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// puts text into the clipboard of the terminal (OSC 52), works over ssh too
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut out = io::stdout();
+    write!(out, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    out.flush()?;
+
+    Ok(())
+}
+
+// opens url with the program the system uses for links; the url is passed as an argument,
+// never through a shell, and only web links are opened
+pub fn open_in_browser(url: &str) -> Result<()> {
+    check_url(url)?;
+
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(windows)]
+    let mut command = Command::new("explorer");
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let mut command = Command::new("xdg-open");
+
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to open {url}"))?;
+
+    // reaped in the background, the opener may wait for the browser
+    std::thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+// chars not allowed in urls (RFC 3986) and special to shells; the url never goes through
+// one, so query strings and escapes (`&`, `%`, `!`) are fine everywhere
+const UNSAFE_URL_CHARS: &str = "\"'`<>\\^{|}$";
+
+fn check_url(url: &str) -> Result<()> {
+    let lower = url.to_ascii_lowercase();
+    if !(lower.starts_with("https://") || lower.starts_with("http://")) {
+        bail!("not a web link: {url}");
+    }
+    if url
+        .chars()
+        .any(|c| c.is_whitespace() || c.is_control() || UNSAFE_URL_CHARS.contains(c))
+    {
+        bail!("unsafe characters in link: {url}");
+    }
+
+    Ok(())
+}
+
+fn base64(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            // chunks shorter than 3 bytes are padded with `=`
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_plain_web_links_are_opened() {
+        assert!(check_url("https://meet.google.com/abc-defg-hij").is_ok());
+        assert!(check_url("HTTP://example.com/a?b=1#c").is_ok());
+
+        for url in [
+            "file:///etc/passwd",
+            "javascript:alert(1)",
+            "calc.exe",
+            "https://example.com/\" & calc",
+            "https://example.com/a|b",
+            "https://example.com/$(id)",
+            "https://example.com/a b",
+            "https://example.com/a\nb",
+        ] {
+            assert!(check_url(url).is_err(), "{url:?}");
+        }
+    }
+
+    #[test]
+    fn query_strings_and_escapes_are_kept() {
+        for url in [
+            "https://zoom.us/j/123?pwd=abc&uname=a%20b",
+            "https://example.com/#!/meeting",
+        ] {
+            assert!(check_url(url).is_ok(), "{url:?}");
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod focusable;
+pub mod links;
 pub mod styles;
+pub mod timeline;
 pub mod utils;
 pub mod view;
@@ -0,0 +1,535 @@
+// Hour grid shared by the daily and weekly views, one row per slot of `slot_minutes`
+
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+};
+
+use crate::{
+    common::calendar::CalendarEvent,
+    config::Config,
+    state::State,
+    ui::common::utils::{event_color, events_on_day, midnight, status_style, truncate},
+};
+
+pub const AXIS_WIDTH: u16 = 6;
+
+// lines of an all-day strip, the rest is summed up in the last one
+pub const ALL_DAY_LINES: usize = 3;
+
+#[derive(Clone, Copy)]
+pub struct TimeGrid {
+    start_hour: u32,
+    end_hour: u32,
+    slot_minutes: u32,
+}
+
+// place of an event in a day column
+pub struct EventBox {
+    // rows `first..end`
+    pub first: usize,
+    pub end: usize,
+    pub column: usize,
+    // columns of the group of events overlapping it
+    pub columns: usize,
+    pub placement: Placement,
+}
+
+// where an event is relative to the hours of the grid, events entirely outside of them
+// are shown on the first or last row with an arrow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    Before,
+    Inside,
+    After,
+}
+
+impl TimeGrid {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            start_hour: config.day_start,
+            end_hour: config.day_end,
+            slot_minutes: config.slot_minutes,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        ((self.end_hour - self.start_hour) * 60 / self.slot_minutes) as usize
+    }
+
+    // `HH:MM` at the start of row
+    pub fn label(&self, row: usize) -> String {
+        let minutes = self.start_hour * 60 + row as u32 * self.slot_minutes;
+        format!("{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+
+    // rows starting on a full hour get a label
+    pub fn is_hour(&self, row: usize) -> bool {
+        (row as u32 * self.slot_minutes).is_multiple_of(60)
+    }
+
+    // row containing given minute of the day, clamped to the grid
+    fn row_of(&self, minute: u32) -> usize {
+        let from_start = minute.saturating_sub(self.start_hour * 60);
+        ((from_start / self.slot_minutes) as usize).min(self.rows().saturating_sub(1))
+    }
+
+    // row containing given time of day, clamped to the grid
+    pub fn row_at(&self, time: NaiveTime) -> usize {
+        self.row_of(time.hour() * 60 + time.minute())
+    }
+
+    // time at the start of row
+    pub fn time_of(&self, row: usize) -> NaiveTime {
+        let minutes = self.start_hour * 60 + row as u32 * self.slot_minutes;
+        NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0).unwrap_or_default()
+    }
+
+    // row of the current time if `day` is today and it is inside the grid
+    pub fn now_row(&self, day: NaiveDate, now: DateTime<Utc>, tz: &Tz) -> Option<usize> {
+        let now = now.with_timezone(tz);
+        let minute = now.hour() * 60 + now.minute();
+
+        (now.date_naive() == day && (self.start_hour * 60..self.end_hour * 60).contains(&minute))
+            .then(|| self.row_of(minute))
+    }
+
+    // rows taken by an event on `day`, at least one; parts outside the grid are clamped to it
+    pub fn event_rows(&self, event: &CalendarEvent, day: NaiveDate, tz: &Tz) -> (usize, usize) {
+        let (start, end) = minutes_on(event, day, tz);
+
+        let first = self.row_of(start);
+        // end is exclusive, a part of a slot takes the whole of it
+        let end = (end.saturating_sub(self.start_hour * 60) as usize)
+            .div_ceil(self.slot_minutes as usize)
+            .clamp(first + 1, self.rows().max(first + 1));
+
+        (first, end)
+    }
+
+    pub fn placement(&self, event: &CalendarEvent, day: NaiveDate, tz: &Tz) -> Placement {
+        let (start, end) = minutes_on(event, day, tz);
+
+        // end is exclusive, an event ending when the grid starts is before it
+        if start < self.start_hour * 60 && end <= self.start_hour * 60 {
+            Placement::Before
+        } else if start >= self.end_hour * 60 {
+            Placement::After
+        } else {
+            Placement::Inside
+        }
+    }
+
+    // boxes of `events` on `day`, overlapping events side by side
+    pub fn layout<'a>(
+        &self,
+        events: impl IntoIterator<Item = &'a CalendarEvent>,
+        day: NaiveDate,
+        tz: &Tz,
+    ) -> Vec<EventBox> {
+        let events: Vec<&CalendarEvent> = events.into_iter().collect();
+        let rows: Vec<(usize, usize)> =
+            events.iter().map(|e| self.event_rows(e, day, tz)).collect();
+
+        overlap_columns(&rows)
+            .into_iter()
+            .zip(rows)
+            .zip(events)
+            .map(|(((column, columns), (first, end)), e)| EventBox {
+                first,
+                end,
+                column,
+                columns,
+                placement: self.placement(e, day, tz),
+            })
+            .collect()
+    }
+}
+
+// start and end of an event as minutes of `day`, parts on other days are clamped to it
+fn minutes_on(event: &CalendarEvent, day: NaiveDate, tz: &Tz) -> (u32, u32) {
+    let minute_of = |t: DateTime<Tz>| match t.date_naive().cmp(&day) {
+        std::cmp::Ordering::Less => 0,
+        std::cmp::Ordering::Equal => t.hour() * 60 + t.minute(),
+        std::cmp::Ordering::Greater => 24 * 60,
+    };
+
+    (
+        minute_of(event.start.in_zone(tz)),
+        minute_of(event.end_utc(tz).with_timezone(tz)),
+    )
+}
+
+// events of a day split into all-day and timed ones, each with its color
+pub struct DayEvents {
+    pub all_day: Vec<(CalendarEvent, Color)>,
+    pub timed: Vec<(CalendarEvent, Color)>,
+    pub boxes: Vec<EventBox>,
+}
+
+impl DayEvents {
+    pub fn load(state: &State, day: NaiveDate) -> Self {
+        let tz = &state.config.timezone;
+        let Some(index) = &state.calendar else {
+            return Self {
+                all_day: vec![],
+                timed: vec![],
+                boxes: vec![],
+            };
+        };
+
+        let (all_day, timed): (Vec<_>, Vec<_>) = events_on_day(index, day, &state.config)
+            .into_iter()
+            .map(|e| {
+                let color = event_color(&e, index.calendar());
+                (e, color)
+            })
+            .partition(|(e, _)| is_all_day_on(e, day, tz));
+        let boxes = TimeGrid::new(&state.config).layout(timed.iter().map(|(e, _)| e), day, tz);
+
+        Self {
+            all_day,
+            timed,
+            boxes,
+        }
+    }
+
+    // events under a cursor row from the left, indices into `all_day` when the cursor is on
+    // the all-day strip (`None`), into `timed` otherwise
+    pub fn under(&self, row: Option<usize>) -> Vec<usize> {
+        match row {
+            None => (0..self.all_day.len()).collect(),
+            Some(row) => {
+                let mut idx: Vec<usize> = (0..self.timed.len())
+                    .filter(|&i| (self.boxes[i].first..self.boxes[i].end).contains(&row))
+                    .collect();
+                idx.sort_by_key(|&i| self.boxes[i].column);
+                idx
+            }
+        }
+    }
+
+    // index of the `column`-th event under the cursor, the last one if there are less
+    pub fn at(&self, row: Option<usize>, column: usize) -> Option<usize> {
+        let under = self.under(row);
+        under
+            .get(column.min(under.len().saturating_sub(1)))
+            .copied()
+    }
+
+    pub fn event_at(&self, row: Option<usize>, column: usize) -> Option<&CalendarEvent> {
+        let i = self.at(row, column)?;
+        match row {
+            None => self.all_day.get(i),
+            Some(_) => self.timed.get(i),
+        }
+        .map(|(e, _)| e)
+    }
+
+    // cursor row and column at the start of the next (or previous) event
+    pub fn jump(&self, row: Option<usize>, forward: bool) -> Option<(usize, usize)> {
+        let starts = self.boxes.iter().map(|b| (b.first, b.column));
+
+        let (first, column) = if forward {
+            starts
+                .filter(|(first, _)| row.is_none_or(|r| *first > r))
+                .min()
+        } else {
+            starts
+                .filter(|(first, _)| row.is_some_and(|r| *first < r))
+                .max_by_key(|(first, column)| (*first, std::cmp::Reverse(*column)))
+        }?;
+
+        // position among events under the cursor there
+        let position = self
+            .under(Some(first))
+            .iter()
+            .filter(|&&i| self.boxes[i].column < column)
+            .count();
+
+        Some((first, position))
+    }
+}
+
+// Column of each range and the number of columns in its group of overlapping ranges.
+// Ranges are taken in order of start, each goes to the first column free by then.
+fn overlap_columns(ranges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..ranges.len()).collect();
+    order.sort_by_key(|&i| ranges[i]);
+
+    let mut result = vec![(0, 1); ranges.len()];
+    // end of the last range in each column of the current group
+    let mut column_ends: Vec<usize> = vec![];
+    let mut group: Vec<usize> = vec![];
+
+    for i in order {
+        let (start, end) = ranges[i];
+
+        // nothing in the group reaches this range, it starts a new one
+        if column_ends.iter().all(|&e| e <= start) {
+            close_group(&mut group, column_ends.len().max(1), &mut result);
+            column_ends.clear();
+        }
+
+        let column = match column_ends.iter().position(|&e| e <= start) {
+            Some(c) => c,
+            None => {
+                column_ends.push(0);
+                column_ends.len() - 1
+            }
+        };
+        column_ends[column] = end;
+        result[i].0 = column;
+        group.push(i);
+    }
+    close_group(&mut group, column_ends.len().max(1), &mut result);
+
+    result
+}
+
+fn close_group(group: &mut Vec<usize>, columns: usize, result: &mut [(usize, usize)]) {
+    for i in group.drain(..) {
+        result[i].1 = columns;
+    }
+}
+
+// all-day events, and events covering the whole of `day`, go above the grid
+pub fn is_all_day_on(event: &CalendarEvent, day: NaiveDate, tz: &Tz) -> bool {
+    let next = day.checked_add_days(Days::new(1)).unwrap_or(day);
+
+    event.is_all_day()
+        || (event.start_utc(tz) <= midnight(day, tz) && event.end_utc(tz) >= midnight(next, tz))
+}
+
+// One all-day event per line, `+N more` in the last line when they do not fit.
+// `selected` is highlighted and always among the shown ones.
+pub fn render_all_day(
+    all_day: &[(CalendarEvent, Color)],
+    area: Rect,
+    selected: Option<usize>,
+    buf: &mut Buffer,
+) {
+    let height = area.height as usize;
+    let shown = if all_day.len() > height {
+        height.saturating_sub(1)
+    } else {
+        all_day.len()
+    };
+    let skip = selected.map_or(0, |s| (s + 1).saturating_sub(shown));
+
+    for (i, (event, color)) in all_day.iter().enumerate().skip(skip).take(shown) {
+        let line = Rect::new(area.x, area.y + (i - skip) as u16, area.width, 1);
+        let mut style = Style::new()
+            .bg(*color)
+            .fg(Color::Black)
+            .patch(status_style(&event.status));
+        if selected == Some(i) {
+            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+
+        buf.set_style(line, style);
+        buf.set_string(
+            line.x,
+            line.y,
+            truncate(
+                event.summary.as_deref().unwrap_or_default(),
+                line.width as usize,
+            ),
+            style,
+        );
+    }
+
+    if shown < all_day.len() && height > 0 {
+        buf.set_stringn(
+            area.x,
+            area.y + shown as u16,
+            format!("+{} more", all_day.len() - shown),
+            area.width as usize,
+            Style::new().dim(),
+        );
+    }
+}
+
+// `HH:MM` labels on full hours of rows `scroll..`, the current time in red
+pub fn render_axis(
+    grid: &TimeGrid,
+    area: Rect,
+    scroll: usize,
+    now: Option<(usize, String)>,
+    buf: &mut Buffer,
+) {
+    for y in 0..area.height {
+        let row = scroll + y as usize;
+        if row >= grid.rows() {
+            break;
+        }
+
+        let (label, style) = match &now {
+            Some((now_row, time)) if *now_row == row => {
+                (time.clone(), Style::new().fg(Color::Red).bold())
+            }
+            _ if grid.is_hour(row) => (grid.label(row), Style::new().fg(Color::Blue)),
+            _ => continue,
+        };
+        buf.set_stringn(area.x, area.y + y, label, area.width as usize, style);
+    }
+}
+
+// red line across the row of the current time, events are drawn over it
+pub fn render_now_line(area: Rect, scroll: usize, now_row: Option<usize>, buf: &mut Buffer) {
+    let Some(y) = now_row.and_then(|r| r.checked_sub(scroll)) else {
+        return;
+    };
+    if y >= area.height as usize {
+        return;
+    }
+
+    let line = "─".repeat(area.width as usize);
+    buf.set_string(area.x, area.y + y as u16, line, Style::new().fg(Color::Red));
+}
+
+// Draws events of one day into `area` showing rows `scroll..`, `selected` is highlighted.
+// Blocks show start time and summary, then location when tall enough.
+pub fn render_events(
+    events: &[(CalendarEvent, Color)],
+    boxes: &[EventBox],
+    area: Rect,
+    scroll: usize,
+    selected: Option<usize>,
+    tz: &Tz,
+    buf: &mut Buffer,
+) {
+    let visible_end = scroll + area.height as usize;
+
+    for (i, ((event, color), b)) in events.iter().zip(boxes).enumerate() {
+        let first = b.first.max(scroll);
+        let end = b.end.min(visible_end);
+        if first >= end {
+            continue;
+        }
+
+        let left = area.width as usize * b.column / b.columns;
+        let right = area.width as usize * (b.column + 1) / b.columns;
+        // gap between columns
+        let width = if b.column + 1 < b.columns {
+            (right - left).saturating_sub(1).max(1)
+        } else {
+            right - left
+        };
+        let block = Rect::new(
+            area.x + left as u16,
+            area.y + (first - scroll) as u16,
+            width as u16,
+            (end - first) as u16,
+        );
+
+        let mut style = Style::new()
+            .bg(*color)
+            .fg(Color::Black)
+            .patch(status_style(&event.status));
+        if selected == Some(i) {
+            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+        buf.set_style(block, style);
+
+        let arrow = match b.placement {
+            Placement::Before => "▴ ",
+            Placement::Inside => "",
+            Placement::After => "▾ ",
+        };
+        let mut lines = vec![format!(
+            "{arrow}{} {}",
+            event.start.in_zone(tz).format("%H:%M"),
+            event.summary.as_deref().unwrap_or_default()
+        )];
+        lines.extend(event.location.clone());
+
+        for (y, line) in (block.y..block.y + block.height).zip(lines) {
+            buf.set_string(block.x, y, truncate(&line, width), style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::gcal::parse_ics;
+
+    #[test]
+    fn separate_ranges_take_one_column() {
+        let columns = overlap_columns(&[(0, 2), (4, 6), (8, 9)]);
+        assert_eq!(columns, [(0, 1), (0, 1), (0, 1)]);
+    }
+
+    #[test]
+    fn touching_ranges_dont_overlap() {
+        // end is exclusive, the second one starts in the row after the first
+        let columns = overlap_columns(&[(0, 2), (2, 4), (4, 6)]);
+        assert_eq!(columns, [(0, 1), (0, 1), (0, 1)]);
+    }
+
+    #[test]
+    fn chained_overlaps_share_columns() {
+        // a overlaps b, b overlaps c, a and c don't: c reuses the column of a
+        let columns = overlap_columns(&[(0, 2), (1, 3), (2, 4)]);
+        assert_eq!(columns, [(0, 2), (1, 2), (0, 2)]);
+
+        // a long range keeps the group open for everything under it
+        let columns = overlap_columns(&[(3, 4), (0, 10), (1, 2), (5, 7), (6, 8), (10, 11)]);
+        assert_eq!(columns, [(1, 3), (0, 3), (1, 3), (1, 3), (2, 3), (0, 1)]);
+    }
+
+    #[test]
+    fn ranges_are_taken_in_order_of_start() {
+        // the shorter of two starting together comes first, the last one fits after it
+        let columns = overlap_columns(&[(2, 4), (0, 3), (0, 1)]);
+        assert_eq!(columns, [(0, 2), (1, 2), (0, 2)]);
+    }
+
+    #[test]
+    fn events_outside_the_grid_are_marked() {
+        let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:early\r
+DTSTART:20251020T060000Z\r
+DTEND:20251020T080000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:morning\r
+DTSTART:20251020T070000Z\r
+DTEND:20251020T090000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:late\r
+DTSTART:20251020T200000Z\r
+DTEND:20251020T210000Z\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+        let calendar = parse_ics(ics.as_bytes()).unwrap();
+        let grid = TimeGrid {
+            start_hour: 8,
+            end_hour: 20,
+            slot_minutes: 30,
+        };
+        let day = NaiveDate::from_ymd_opt(2025, 10, 20).unwrap();
+
+        let boxes = grid.layout(&calendar.events, day, &Tz::UTC);
+        let placed: Vec<_> = boxes
+            .iter()
+            .map(|b| (b.first, b.end, b.placement))
+            .collect();
+        assert_eq!(
+            placed,
+            [
+                (0, 1, Placement::Before),
+                (0, 2, Placement::Inside),
+                (23, 24, Placement::After),
+            ]
+        );
+    }
+}
@@ -1,4 +1,13 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use ratatui::style::{Color, Style, Stylize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    common::calendar::{Calendar, CalendarEvent, CalendarQuery, EventStatus, RawProperty},
+    config::Config,
+};
 
 // returns:
 // 1. first day number (Mon-Sat) [0;6]
@@ -25,3 +34,105 @@ pub fn month_info(year: i32, month: u32) -> (i32, i32) {
 
     (n, days_in_month as i32)
 }
+
+// events overlapping days `[first, last]` in configured timezone (recurring events expanded),
+// sorted by start
+pub fn events_between(
+    calendar: &impl CalendarQuery,
+    first: NaiveDate,
+    last: NaiveDate,
+    config: &Config,
+) -> Vec<CalendarEvent> {
+    let tz = &config.timezone;
+    let from = midnight(first, tz);
+    let to = midnight(last.succ_opt().unwrap_or(last), tz);
+
+    calendar
+        .events_in_range(from, to)
+        .into_iter()
+        .filter(|e| !(config.hide_cancelled && e.status == EventStatus::Cancelled))
+        .filter(|e| match &config.email {
+            Some(email) if config.only_accepted => e.is_accepted_by(email),
+            _ => true,
+        })
+        .collect()
+}
+
+// events overlapping given day in configured timezone (recurring events expanded), sorted by start
+pub fn events_on_day(
+    calendar: &impl CalendarQuery,
+    day: NaiveDate,
+    config: &Config,
+) -> Vec<CalendarEvent> {
+    events_between(calendar, day, day, config)
+}
+
+// cancelled events are struck-through and dimmed, tentative ones italic
+pub fn status_style(status: &EventStatus) -> Style {
+    match status {
+        EventStatus::Cancelled => Style::new().crossed_out().dim(),
+        EventStatus::Tentative => Style::new().italic(),
+        EventStatus::Confirmed | EventStatus::Unknown => Style::new(),
+    }
+}
+
+pub fn midnight(day: NaiveDate, tz: &Tz) -> DateTime<Utc> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+pub fn today(tz: &Tz) -> NaiveDate {
+    Utc::now().with_timezone(tz).date_naive()
+}
+
+// COLOR (RFC 7986) of the event, else of its calendar (COLOR or X-APPLE-CALENDAR-COLOR),
+// yellow when neither is set or understood
+pub fn event_color(event: &CalendarEvent, calendar: &Calendar) -> Color {
+    let color_of = |extra: &[RawProperty], names: &[&str]| {
+        extra
+            .iter()
+            .filter(|p| names.iter().any(|n| p.name.eq_ignore_ascii_case(n)))
+            .find_map(|p| parse_color(&p.value))
+    };
+
+    color_of(&event.extra, &["COLOR"])
+        .or_else(|| color_of(&calendar.extra, &["COLOR", "X-APPLE-CALENDAR-COLOR"]))
+        .unwrap_or(Color::Yellow)
+}
+
+// CSS color name or `#rrggbb`, alpha of `#rrggbbaa` is dropped
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    let value = match value.strip_prefix('#') {
+        Some(hex) if hex.len() == 8 => &value[..7],
+        _ => value,
+    };
+
+    value.to_lowercase().parse().ok()
+}
+
+// `s` cut to `width` columns, ending with `…` when cut
+pub fn truncate(s: &str, width: usize) -> String {
+    if s.width() <= width {
+        return s.to_string();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if width > 0 {
+        out.push('…');
+    }
+
+    out
+}
@@ -1,14 +1,56 @@
 use anyhow::Result;
 
-use ratatui::{buffer::Buffer, crossterm::event::Event, layout::Rect};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode},
+    layout::Rect,
+};
 
 use crate::ui::common::focusable::Focusable;
 
+/// Views the view switcher can be asked to show, see [`View::take_switch_request`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewKind {
+    Monthly,
+    Weekly,
+    Daily,
+    Agenda,
+    Tasks,
+    Yearly,
+}
+
 // extended widget trait
 pub trait View {
     fn handle_event(&mut self, e: &Event) -> Result<()>;
     fn update(&mut self);
     fn render(&self, area: Rect, buf: &mut Buffer);
+
+    /// Whether the view takes every key press right now (e.g. while text is typed into it),
+    /// so keys bound higher up (quit, popups, focus switching) are passed down instead
+    fn captures_input(&self) -> bool {
+        false
+    }
+
+    /// Whether the view uses given key itself, so a binding higher up (e.g. a popup
+    /// trigger) leaves it to the view
+    fn handles_key(&self, _key: KeyCode) -> bool {
+        false
+    }
+
+    /// Whether the view has nothing to show right now, a popup trigger leaves it closed
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Which of the views this is, for switching to it by kind
+    fn kind(&self) -> Option<ViewKind> {
+        None
+    }
+
+    /// View the user asked to go to from this one, taken by the view switcher after `update`
+    fn take_switch_request(&mut self) -> Option<ViewKind> {
+        None
+    }
 }
 
 pub trait FocusableView: View + Focusable {
@@ -0,0 +1,407 @@
+use std::cell::Cell;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+use crate::{
+    common::calendar::{Attendee, CalendarEvent, EventStatus, PartStat, Role},
+    config::Config,
+    providers::gcal::write_event_ics,
+    state::AppState,
+    ui::{
+        common::{
+            focusable::Focusable,
+            links::{copy_to_clipboard, open_in_browser},
+            utils::status_style,
+            view::{
+                FocusableView, FocusableViewWithCursorControl, Resettable, View,
+                ViewWithCursorControl,
+            },
+        },
+        components::popup_with_cursor::Popup,
+    },
+};
+
+// Details of `State::selected_event`, or its iCalendar source
+pub struct EventPopupContent {
+    state: AppState,
+    focused: bool,
+
+    // iCalendar source instead of the details
+    raw: bool,
+    // link under the cursor, index into `CalendarEvent::urls`
+    link: usize,
+    // outcome of the last copy or open, replaces the key help
+    status: Option<String>,
+
+    // first visible line and lines that fit, set while rendering
+    scroll: Cell<usize>,
+    height: Cell<usize>,
+    // scroll to the link under the cursor on the next render
+    reveal_link: Cell<bool>,
+}
+
+impl EventPopupContent {
+    fn urls(&self) -> Vec<String> {
+        self.state
+            .state
+            .borrow()
+            .selected_event
+            .as_ref()
+            .map(|e| e.urls())
+            .unwrap_or_default()
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        let (scroll, page) = (self.scroll.get(), self.height.get().max(1));
+        self.status = None;
+
+        match code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll.set(scroll.saturating_add(1)),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll.set(scroll.saturating_sub(1)),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.scroll.set(scroll.saturating_add(page)),
+            KeyCode::PageUp => self.scroll.set(scroll.saturating_sub(page)),
+            KeyCode::Char('g') | KeyCode::Home => self.scroll.set(0),
+            // clamped while rendering
+            KeyCode::Char('G') | KeyCode::End => self.scroll.set(usize::MAX),
+
+            KeyCode::Char('r') => {
+                self.raw = !self.raw;
+                self.scroll.set(0);
+            }
+
+            KeyCode::Tab | KeyCode::BackTab if !self.raw => {
+                let count = self.urls().len();
+                if count > 0 {
+                    self.link = if code == KeyCode::Tab {
+                        (self.link + 1) % count
+                    } else {
+                        (self.link + count - 1) % count
+                    };
+                    self.reveal_link.set(true);
+                }
+            }
+            KeyCode::Char(c @ ('o' | 'y')) if !self.raw => {
+                let Some(url) = self.urls().into_iter().nth(self.link) else {
+                    return;
+                };
+
+                let result = if c == 'o' {
+                    open_in_browser(&url).map(|_| format!("Opened {url}"))
+                } else {
+                    copy_to_clipboard(&url).map(|_| format!("Copied {url}"))
+                };
+                self.status = Some(result.unwrap_or_else(|e| format!("{e:#}")));
+            }
+            _ => {}
+        }
+    }
+
+    // last line: outcome of the last action or the keys
+    fn footer(&self, has_links: bool) -> Line<'static> {
+        if let Some(status) = &self.status {
+            return Line::from(status.clone()).fg(Color::Yellow);
+        }
+
+        let keys = if self.raw {
+            "j/k scroll  r details"
+        } else if has_links {
+            "j/k scroll  tab next link  o open  y copy  r source"
+        } else {
+            "j/k scroll  r source"
+        };
+        Line::from(keys).dim()
+    }
+}
+
+impl View for EventPopupContent {
+    fn handle_event(&mut self, e: &Event) -> anyhow::Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            self.handle_key(key_ev.code);
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) {}
+
+    // opened only for a selected event
+    fn is_empty(&self) -> bool {
+        self.state.state.borrow().selected_event.is_none()
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let state = self.state.state.borrow();
+
+        let Some(event) = &state.selected_event else {
+            Paragraph::new(Line::from("No event selected").dim()).render(area, buf);
+            return;
+        };
+
+        let urls = event.urls();
+        let (lines, link_lines) = if self.raw {
+            // instances are written as the series or override they come from
+            let stored = state.calendar.as_ref().and_then(|c| c.stored(event));
+            let source = write_event_ics(stored.unwrap_or(event));
+            (
+                source.lines().map(|l| Line::from(l.to_string())).collect(),
+                vec![],
+            )
+        } else {
+            let link = self.focused.then_some(self.link);
+            event_details(event, &state.config, &urls, link)
+        };
+
+        let body = Rect {
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+        let height = body.height as usize;
+        self.height.set(height);
+
+        let wrapped = |lines: Vec<Line<'static>>| Paragraph::new(lines).wrap(Wrap { trim: false });
+
+        // lines are counted after wrapping
+        let mut scroll = self.scroll.get();
+        if self.reveal_link.take()
+            && let Some(&line) = link_lines.get(self.link)
+        {
+            let row = wrapped(lines[..line].to_vec()).line_count(body.width);
+            scroll = scroll.min(row).max((row + 1).saturating_sub(height));
+        }
+        let paragraph = wrapped(lines);
+        let scroll = scroll.min(paragraph.line_count(body.width).saturating_sub(height));
+        self.scroll.set(scroll);
+
+        paragraph.scroll((scroll as u16, 0)).render(body, buf);
+
+        if area.height > 0 {
+            self.footer(!urls.is_empty()).render(
+                Rect::new(area.x, area.y + area.height - 1, area.width, 1),
+                buf,
+            );
+        }
+    }
+}
+
+impl Focusable for EventPopupContent {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = !self.focused;
+    }
+}
+
+impl FocusableView for EventPopupContent {
+    fn handle_event_if_focused(&mut self, e: &Event) -> anyhow::Result<()> {
+        if self.focused {
+            self.handle_event(e)?;
+        }
+        Ok(())
+    }
+}
+
+impl ViewWithCursorControl for EventPopupContent {
+    fn render_with_cursor(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        _set_cursor: &mut dyn FnMut(u16, u16),
+    ) {
+        self.render(area, buf);
+    }
+}
+
+impl FocusableViewWithCursorControl for EventPopupContent {}
+
+impl Resettable for EventPopupContent {
+    fn reset(&mut self) -> anyhow::Result<()> {
+        self.raw = false;
+        self.link = 0;
+        self.status = None;
+        self.scroll.set(0);
+        self.reveal_link.set(false);
+        Ok(())
+    }
+}
+
+pub fn new_event_popup<'a>(state: AppState) -> Popup<'a, EventPopupContent> {
+    Popup::new(
+        "Event",
+        EventPopupContent {
+            state,
+            focused: false,
+            raw: false,
+            link: 0,
+            status: None,
+            scroll: Cell::new(0),
+            height: Cell::new(0),
+            reveal_link: Cell::new(false),
+        },
+        None,
+        None,
+    )
+}
+
+// details with the line of each link, `link` is highlighted
+fn event_details(
+    event: &CalendarEvent,
+    config: &Config,
+    urls: &[String],
+    link: Option<usize>,
+) -> (Vec<Line<'static>>, Vec<usize>) {
+    let tz = &config.timezone;
+    let label =
+        |s: &'static str| Span::styled(format!("{s:<10}"), Style::default().fg(Color::Blue));
+
+    let mut lines = vec![
+        Line::styled(
+            event.summary.clone().unwrap_or_default(),
+            status_style(&event.status).bold(),
+        ),
+        Line::default(),
+    ];
+
+    let when = if event.is_all_day() {
+        let (first, last) = event.days(tz);
+        if first == last {
+            format!("{}", first.format("%a %d %b %Y"))
+        } else {
+            format!(
+                "{} – {}",
+                first.format("%a %d %b"),
+                last.format("%a %d %b %Y")
+            )
+        }
+    } else {
+        let start = event.start.in_zone(tz);
+        let end = event.end.in_zone(tz);
+        if start.date_naive() == end.date_naive() {
+            format!(
+                "{} – {}",
+                start.format("%a %d %b %Y %H:%M"),
+                end.format("%H:%M")
+            )
+        } else {
+            format!(
+                "{} – {}",
+                start.format("%a %d %b %H:%M"),
+                end.format("%a %d %b %Y %H:%M")
+            )
+        }
+    };
+    lines.push(Line::from(vec![label("When"), when.into()]));
+
+    if let Some(status) = match event.status {
+        EventStatus::Confirmed => Some("confirmed"),
+        EventStatus::Cancelled => Some("cancelled"),
+        EventStatus::Tentative => Some("tentative"),
+        EventStatus::Unknown => None,
+    } {
+        lines.push(Line::from(vec![label("Status"), status.into()]));
+    }
+
+    if let Some(location) = &event.location {
+        lines.push(Line::from(vec![label("Where"), location.clone().into()]));
+    }
+
+    if let Some(organizer) = &event.organizer {
+        lines.push(Line::from(vec![
+            label("Organizer"),
+            person(organizer.name.as_deref(), &organizer.address).into(),
+        ]));
+    }
+
+    if !event.attendees.is_empty() {
+        lines.push(Line::from(label("Attendees")));
+        lines.extend(
+            event
+                .attendees
+                .iter()
+                .map(|a| attendee_line(a, config.email.as_deref())),
+        );
+    }
+
+    // whole links on lines of their own, terminals let them be clicked
+    let mut link_lines = vec![];
+    if !urls.is_empty() {
+        lines.push(Line::from(label("Links")));
+        for (i, url) in urls.iter().enumerate() {
+            link_lines.push(lines.len());
+
+            let style = Style::default().fg(Color::Cyan).underlined();
+            let style = if link == Some(i) {
+                style.reversed()
+            } else {
+                style
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(url.clone(), style),
+            ]));
+        }
+    }
+
+    if let Some(description) = &event.description {
+        lines.push(Line::default());
+        lines.extend(description.lines().map(|l| Line::from(l.to_string())));
+    }
+
+    (lines, link_lines)
+}
+
+fn attendee_line(attendee: &Attendee, email: Option<&str>) -> Line<'static> {
+    let (mark, color) = match attendee.partstat {
+        PartStat::Accepted => ("✓", Color::Green),
+        PartStat::Declined => ("✗", Color::Red),
+        PartStat::Tentative => ("?", Color::Yellow),
+        PartStat::Delegated => ("→", Color::Gray),
+        PartStat::NeedsAction | PartStat::Other(_) => ("·", Color::Gray),
+    };
+
+    let mut spans = vec![
+        Span::styled(format!("  {mark} "), Style::default().fg(color)),
+        person(attendee.name.as_deref(), &attendee.address).into(),
+    ];
+
+    match attendee.role {
+        Role::Chair => spans.push(" (chair)".dim()),
+        Role::Optional => spans.push(" (optional)".dim()),
+        Role::NonParticipant => spans.push(" (for information)".dim()),
+        Role::Required | Role::Other(_) => {}
+    }
+    if attendee.rsvp && attendee.partstat == PartStat::NeedsAction {
+        spans.push(" reply requested".italic());
+    }
+    if email.is_some_and(|email| attendee.is(email)) {
+        spans.push(" (you)".bold());
+    }
+
+    Line::from(spans)
+}
+
+fn person(name: Option<&str>, address: &str) -> String {
+    let email = address
+        .strip_prefix("mailto:")
+        .or_else(|| address.strip_prefix("MAILTO:"))
+        .unwrap_or(address);
+
+    match name {
+        Some(name) if name != email => format!("{name} <{email}>"),
+        _ => email.to_string(),
+    }
+}
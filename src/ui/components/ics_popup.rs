@@ -1,6 +1,16 @@
-use ratatui::crossterm::event::{Event, KeyCode};
+use std::thread;
+
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
 
 use crate::{
+    message::AppMessage,
+    providers::gcal::import_gcal,
     state::AppState,
     ui::{
         common::{
@@ -10,7 +20,10 @@ use crate::{
                 ViewWithCursorControl,
             },
         },
-        components::{input::UserInput, popup_with_cursor::Popup},
+        components::{
+            input::{UserInput, expand_home},
+            popup_with_cursor::Popup,
+        },
     },
 };
 
@@ -19,18 +32,67 @@ pub struct IcsPopupContent {
     input: UserInput,
 }
 
+impl IcsPopupContent {
+    // parses the file in background, result comes back as a message
+    fn load(&mut self) -> anyhow::Result<()> {
+        let path = expand_home(self.input.content().trim());
+        // history is a convenience, not worth failing the load over
+        let _ = self.input.push_history();
+
+        let mut state = self.state.state.try_borrow_mut()?;
+        state.loading = Some(path.clone());
+
+        let tx = self.state.messages.clone();
+        thread::spawn(move || {
+            let result = import_gcal(path.clone());
+            // app is gone if nobody receives
+            let _ = tx.send(AppMessage::CalendarLoaded { path, result });
+        });
+
+        Ok(())
+    }
+
+    fn status_line(&self) -> Line<'static> {
+        let state = self.state.state.borrow();
+
+        if !self.input.completions().is_empty() {
+            Line::styled(
+                self.input.completions().join("  "),
+                Style::default().fg(Color::DarkGray),
+            )
+        } else if let Some(path) = &state.loading {
+            Line::styled(
+                format!("Loading {}...", path.display()),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if let Some(err) = &state.load_error {
+            Line::styled(err.clone(), Style::default().fg(Color::Red))
+        } else if let Some(calendar) = &state.calendar {
+            Line::styled(
+                format!("Loaded {} events", calendar.calendar().events.len()),
+                Style::default().fg(Color::Green),
+            )
+        } else {
+            Line::default()
+        }
+    }
+
+    fn split(area: Rect) -> (Rect, Rect) {
+        let input_h = area.height.min(3);
+        (
+            Rect::new(area.x, area.y, area.width, input_h),
+            Rect::new(area.x, area.y + input_h, area.width, area.height - input_h),
+        )
+    }
+}
+
 impl View for IcsPopupContent {
     fn handle_event(&mut self, e: &ratatui::crossterm::event::Event) -> anyhow::Result<()> {
-        match e {
-            Event::Key(key_ev) => match key_ev.code {
-                KeyCode::Enter => {
-                    self.state.state.try_borrow_mut()?.tmp =
-                        Some(String::from(self.input.content()))
-                }
-
-                _ => {}
-            },
-            _ => {}
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+            && key_ev.code == KeyCode::Enter
+        {
+            self.load()?;
         }
 
         Ok(())
@@ -40,8 +102,13 @@ impl View for IcsPopupContent {
         self.input.update();
     }
 
+    // path is typed right away
+    fn captures_input(&self) -> bool {
+        true
+    }
+
     fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
-        self.input.render(area, buf);
+        self.render_with_cursor(area, buf, &mut |_x, _y| {});
     }
 }
 impl Focusable for IcsPopupContent {
@@ -75,7 +142,10 @@ impl ViewWithCursorControl for IcsPopupContent {
         buf: &mut ratatui::prelude::Buffer,
         set_cursor: &mut dyn FnMut(u16, u16),
     ) {
-        self.input.render_with_cursor(area, buf, set_cursor);
+        let (input_area, status_area) = Self::split(area);
+
+        self.input.render_with_cursor(input_area, buf, set_cursor);
+        Paragraph::new(self.status_line()).render(status_area, buf);
     }
 }
 impl FocusableViewWithCursorControl for IcsPopupContent {}
@@ -90,7 +160,9 @@ pub fn new_ics_popup<'a>(state: AppState) -> Popup<'a, IcsPopupContent> {
         "Your .ics file",
         IcsPopupContent {
             state,
-            input: UserInput::new(""),
+            input: UserInput::new("")
+                .with_history("ics_path")
+                .with_path_completion(),
         },
         None,
         None,
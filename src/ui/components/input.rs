@@ -1,3 +1,5 @@
+use std::{cell::Cell, fs, path::PathBuf};
+
 use anyhow::Result;
 use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::prelude::Widget;
@@ -9,16 +11,48 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::common::history::History;
 use crate::ui::common::focusable::{FocusStatus, Focusable};
 use crate::ui::common::view::{
     FocusableView, FocusableViewWithCursorControl, Resettable, View, ViewWithCursorControl,
 };
 
+// Single-line input with emacs-style editing keys:
+//
+//     C-a / C-e        start / end of line
+//     C-b / C-f        char left / right
+//     M-b / M-f        word left / right (also C-Left / C-Right)
+//     C-h / C-d        delete char before / after cursor
+//     C-w / M-Bksp     delete word before cursor (up to a space / up to punctuation)
+//     M-d              delete word after cursor
+//     C-u / C-k        delete to start / end of line
+//     C-y              paste last deleted text
+//     Up / Down        history (also C-p / C-n)
+//     Tab              path completion, if enabled
 pub struct UserInput {
     title: String,
     input: String,
+    // byte index into `input`, always on a grapheme boundary
     cursor_position: usize,
     focused: bool,
+
+    // last text deleted with a word or line key
+    killed: String,
+
+    history: History,
+    // entry shown while going through history, and the text typed before
+    history_pos: Option<usize>,
+    draft: String,
+
+    path_completion: bool,
+    // candidates of the last ambiguous completion
+    completions: Vec<String>,
+
+    // first display column shown, set while rendering so the cursor stays visible
+    scroll: Cell<usize>,
 }
 
 impl UserInput {
@@ -28,52 +62,328 @@ impl UserInput {
             input: String::new(),
             cursor_position: 0,
             focused: true,
+            killed: String::new(),
+            history: History::new(),
+            history_pos: None,
+            draft: String::new(),
+            path_completion: false,
+            completions: vec![],
+            scroll: Cell::new(0),
         }
     }
 
+    /// Keeps entries in history saved under `name`, see [`UserInput::push_history`]
+    pub fn with_history(mut self, name: &str) -> Self {
+        self.history = History::load(name);
+        self
+    }
+
+    /// Tab completes file system paths
+    pub fn with_path_completion(mut self) -> Self {
+        self.path_completion = true;
+        self
+    }
+
     pub fn content(&self) -> &str {
         &self.input
     }
+
+    /// Candidates left by the last Tab, when it could not pick one
+    pub fn completions(&self) -> &[String] {
+        &self.completions
+    }
+
+    /// Adds current text to history, to be called when it is submitted
+    pub fn push_history(&mut self) -> Result<()> {
+        self.history_pos = None;
+        self.history.push(&self.input)
+    }
+
+    fn set_content(&mut self, text: String) {
+        self.input = text;
+        self.cursor_position = self.input.len();
+    }
+
+    // start of the grapheme before the cursor
+    fn prev_boundary(&self) -> Option<usize> {
+        self.input[..self.cursor_position]
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(i, _)| i)
+    }
+
+    // end of the grapheme after the cursor
+    fn next_boundary(&self) -> Option<usize> {
+        self.input[self.cursor_position..]
+            .graphemes(true)
+            .next()
+            .map(|g| self.cursor_position + g.len())
+    }
+
+    // start of the word before the cursor, words are runs of graphemes matching `is_word`
+    fn word_start(&self, is_word: fn(&str) -> bool) -> usize {
+        let mut graphemes = self.input[..self.cursor_position]
+            .grapheme_indices(true)
+            .rev()
+            .skip_while(|(_, g)| !is_word(g))
+            .peekable();
+
+        let mut start = graphemes.peek().map_or(0, |(i, _)| *i);
+        for (i, g) in graphemes {
+            if !is_word(g) {
+                break;
+            }
+            start = i;
+        }
+
+        start
+    }
+
+    // end of the word after the cursor
+    fn word_end(&self, is_word: fn(&str) -> bool) -> usize {
+        let mut end = self.cursor_position;
+        let mut in_word = false;
+
+        for g in self.input[self.cursor_position..].graphemes(true) {
+            if is_word(g) {
+                in_word = true;
+            } else if in_word {
+                break;
+            }
+            end += g.len();
+        }
+
+        end
+    }
+
+    fn insert(&mut self, c: char) {
+        self.input.insert(self.cursor_position, c);
+        self.cursor_position += c.len_utf8();
+
+        // typed before a combining mark the char becomes part of its grapheme,
+        // cursor goes after the whole of it
+        if let Some((i, g)) = self
+            .input
+            .grapheme_indices(true)
+            .find(|(i, g)| *i < self.cursor_position && self.cursor_position < i + g.len())
+        {
+            self.cursor_position = i + g.len();
+        }
+    }
+
+    fn insert_str(&mut self, s: &str) {
+        // single line, pasted line breaks become spaces
+        for c in s.chars() {
+            match c {
+                '\r' => {}
+                '\n' => self.insert(' '),
+                c => self.insert(c),
+            }
+        }
+    }
+
+    // deletes `from..to`, remembering the text for C-y
+    fn kill(&mut self, from: usize, to: usize) {
+        if from < to {
+            self.killed = self.input[from..to].to_string();
+            self.input.replace_range(from..to, "");
+            self.cursor_position = from;
+        }
+    }
+
+    fn backspace(&mut self) {
+        if let Some(prev) = self.prev_boundary() {
+            self.input.replace_range(prev..self.cursor_position, "");
+            self.cursor_position = prev;
+        }
+    }
+
+    fn delete(&mut self) {
+        if let Some(next) = self.next_boundary() {
+            self.input.replace_range(self.cursor_position..next, "");
+        }
+    }
+
+    fn move_left(&mut self) {
+        if let Some(prev) = self.prev_boundary() {
+            self.cursor_position = prev;
+        }
+    }
+
+    fn move_right(&mut self) {
+        if let Some(next) = self.next_boundary() {
+            self.cursor_position = next;
+        }
+    }
+
+    fn history_prev(&mut self) {
+        let entries = self.history.entries();
+        let pos = match self.history_pos {
+            None if entries.is_empty() => return,
+            None => {
+                self.draft = self.input.clone();
+                entries.len() - 1
+            }
+            Some(pos) => pos.saturating_sub(1),
+        };
+
+        self.history_pos = Some(pos);
+        self.set_content(self.history.entries()[pos].clone());
+    }
+
+    fn history_next(&mut self) {
+        let Some(pos) = self.history_pos else {
+            return;
+        };
+
+        if pos + 1 < self.history.entries().len() {
+            self.history_pos = Some(pos + 1);
+            self.set_content(self.history.entries()[pos + 1].clone());
+        } else {
+            self.history_pos = None;
+            let draft = std::mem::take(&mut self.draft);
+            self.set_content(draft);
+        }
+    }
+
+    fn complete(&mut self) {
+        let (completed, candidates) = complete_path(&self.input);
+        self.set_content(completed);
+        self.completions = candidates;
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        let alt = modifiers.contains(KeyModifiers::ALT);
+
+        if code != KeyCode::Tab {
+            self.completions.clear();
+        }
+
+        match code {
+            KeyCode::Char('a') if ctrl => self.cursor_position = 0,
+            KeyCode::Char('e') if ctrl => self.cursor_position = self.input.len(),
+            KeyCode::Char('b') if ctrl => self.move_left(),
+            KeyCode::Char('f') if ctrl => self.move_right(),
+            KeyCode::Char('h') if ctrl => self.backspace(),
+            KeyCode::Char('d') if ctrl => self.delete(),
+            KeyCode::Char('w') if ctrl => {
+                self.kill(self.word_start(is_not_space), self.cursor_position)
+            }
+            KeyCode::Char('u') if ctrl => self.kill(0, self.cursor_position),
+            KeyCode::Char('k') if ctrl => self.kill(self.cursor_position, self.input.len()),
+            KeyCode::Char('y') if ctrl => self.insert_str(&self.killed.clone()),
+            KeyCode::Char('p') if ctrl => self.history_prev(),
+            KeyCode::Char('n') if ctrl => self.history_next(),
+            // ignore other control + char input
+            KeyCode::Char(_) if ctrl => {}
+
+            KeyCode::Char('b') if alt => self.cursor_position = self.word_start(is_alphanumeric),
+            KeyCode::Char('f') if alt => self.cursor_position = self.word_end(is_alphanumeric),
+            KeyCode::Char('d') if alt => {
+                self.kill(self.cursor_position, self.word_end(is_alphanumeric))
+            }
+            KeyCode::Backspace if alt => {
+                self.kill(self.word_start(is_alphanumeric), self.cursor_position)
+            }
+            KeyCode::Left if ctrl || alt => self.cursor_position = self.word_start(is_alphanumeric),
+            KeyCode::Right if ctrl || alt => self.cursor_position = self.word_end(is_alphanumeric),
+
+            KeyCode::Char(c) => self.insert(c),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.cursor_position = 0,
+            KeyCode::End => self.cursor_position = self.input.len(),
+            KeyCode::Up => self.history_prev(),
+            KeyCode::Down => self.history_next(),
+            KeyCode::Tab if self.path_completion => self.complete(),
+            _ => {}
+        }
+    }
+}
+
+fn is_not_space(g: &str) -> bool {
+    !g.chars().all(char::is_whitespace)
+}
+
+fn is_alphanumeric(g: &str) -> bool {
+    g.chars().next().is_some_and(char::is_alphanumeric)
+}
+
+/// Path with leading `~` replaced by the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+// Completes the last component of `input` to the longest prefix shared by the matching
+// entries of its directory. Returns completed text and the entries if more than one matched.
+fn complete_path(input: &str) -> (String, Vec<String>) {
+    let (dir, partial) = match input.rfind('/') {
+        Some(i) => (&input[..=i], &input[i + 1..]),
+        None => ("", input),
+    };
+
+    let dir_path = if dir.is_empty() {
+        PathBuf::from(".")
+    } else {
+        expand_home(dir)
+    };
+    let Ok(entries) = fs::read_dir(dir_path) else {
+        return (input.to_string(), vec![]);
+    };
+
+    let mut candidates: Vec<String> = entries
+        .filter_map(|e| {
+            let e = e.ok()?;
+            let mut name = e.file_name().into_string().ok()?;
+            // hidden entries only when asked for
+            if !name.starts_with(partial) || (name.starts_with('.') && !partial.starts_with('.')) {
+                return None;
+            }
+            if e.path().is_dir() {
+                name.push('/');
+            }
+            Some(name)
+        })
+        .collect();
+    candidates.sort();
+
+    let Some(first) = candidates.first() else {
+        return (input.to_string(), vec![]);
+    };
+
+    let common = candidates.iter().skip(1).fold(first.as_str(), |common, c| {
+        let len = common
+            .char_indices()
+            .zip(c.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((i, a), _)| i + a.len_utf8());
+        &common[..len]
+    });
+    let completed = format!("{dir}{common}");
+
+    if candidates.len() == 1 {
+        (completed, vec![])
+    } else {
+        (completed, candidates)
+    }
 }
 
 impl View for UserInput {
     fn handle_event(&mut self, e: &Event) -> Result<()> {
         match e {
             Event::Key(key_ev) if key_ev.kind == KeyEventKind::Press => {
-                match key_ev.code {
-                    KeyCode::Char(c) => {
-                        if key_ev.modifiers.contains(KeyModifiers::CONTROL) {
-                            // ignore control + char input
-                        } else {
-                            self.input.insert(self.cursor_position, c);
-                            self.cursor_position += 1;
-                        }
-                    }
-                    KeyCode::Backspace => {
-                        if self.cursor_position > 0 {
-                            self.cursor_position -= 1;
-                            self.input.remove(self.cursor_position);
-                        }
-                    }
-                    KeyCode::Left => {
-                        if self.cursor_position > 0 {
-                            self.cursor_position -= 1;
-                        }
-                    }
-                    KeyCode::Right => {
-                        if self.cursor_position < self.input.len() {
-                            self.cursor_position += 1;
-                        }
-                    }
-                    KeyCode::Home => {
-                        self.cursor_position = 0;
-                    }
-                    KeyCode::End => {
-                        self.cursor_position = self.input.len();
-                    }
-                    _ => {}
-                }
+                self.handle_key(key_ev.code, key_ev.modifiers)
             }
+            Event::Paste(text) => self.insert_str(text),
             _ => (),
         }
         Ok(())
@@ -93,21 +403,50 @@ impl ViewWithCursorControl for UserInput {
         buf: &mut Buffer,
         set_cursor: &mut dyn FnMut(u16, u16),
     ) {
-        let text = Span::raw(&self.input);
         let block = Block::default()
             .title(self.title.as_str())
             .borders(Borders::ALL)
             .border_style(Style::default());
+        let inner = block.inner(area);
+        if inner.width == 0 || inner.height == 0 {
+            block.render(area, buf);
+            return;
+        }
+        let width = inner.width as usize;
+
+        // wide characters take two cells
+        let cursor_col = self.input[..self.cursor_position].width();
+        // scrolled just enough for the cursor to be visible
+        let scroll = self
+            .scroll
+            .get()
+            .min(cursor_col)
+            .max((cursor_col + 1).saturating_sub(width));
+        self.scroll.set(scroll);
+
+        let mut col = 0;
+        let mut visible = String::new();
+        for g in self.input.graphemes(true) {
+            let start = col;
+            col += g.width();
+
+            if start >= scroll && col <= scroll + width {
+                visible.push_str(g);
+            } else if start < scroll && col > scroll {
+                // the half of a wide character cut off by the scroll is left blank, the
+                // ones after it stay under their column
+                visible.extend(std::iter::repeat_n(' ', col - scroll));
+            }
+        }
 
-        let paragraph = Paragraph::new(text)
+        let paragraph = Paragraph::new(Span::raw(visible))
             .block(block)
-            .style(Style::default())
-            .scroll((0, 0));
+            .style(Style::default());
 
         paragraph.render(area, buf);
 
         if self.focused {
-            let x = area.x + self.cursor_position as u16 + 1;
+            let x = area.x + (cursor_col - scroll) as u16 + 1;
             let y = area.y + 1;
             set_cursor(x, y)
         }
@@ -144,6 +483,9 @@ impl Resettable for UserInput {
     fn reset(&mut self) -> Result<()> {
         self.input = String::new();
         self.cursor_position = 0;
+        self.history_pos = None;
+        self.completions.clear();
+        self.scroll.set(0);
 
         Ok(())
     }
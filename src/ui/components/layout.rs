@@ -6,7 +6,7 @@ use ratatui::{
 
 use crate::ui::common::{
     focusable::{FocusStatus, Focusable},
-    view::{FocusableView, View},
+    view::{FocusableView, View, ViewWithCursorControl},
 };
 
 pub struct Layout<V, J>
@@ -40,6 +40,29 @@ where
         self.v.toggle_focus();
         self.j.toggle_focus();
     }
+
+    // areas of both parts, one column between them is left for the divider
+    fn split(area: Rect) -> (Rect, Rect) {
+        let left = area.width * 3 / 4;
+
+        // right part takes what is left after the line, quarters may not add up to the width
+        (
+            Rect::new(area.x, area.y, left, area.height),
+            Rect::new(
+                area.x + left + 1,
+                area.y,
+                area.width.saturating_sub(left + 1),
+                area.height,
+            ),
+        )
+    }
+
+    fn render_divider(area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let (v_area, _) = Self::split(area);
+        if v_area.right() < area.right() {
+            render_vertical_line(Rect::new(v_area.right(), area.y, 1, area.height), buf);
+        }
+    }
 }
 
 impl<V, J> View for Layout<V, J>
@@ -49,10 +72,13 @@ where
 {
     fn handle_event(&mut self, e: &ratatui::crossterm::event::Event) -> anyhow::Result<()> {
         match e {
-            Event::Key(key_ev) if key_ev.kind == KeyEventKind::Press => match key_ev.code {
-                KeyCode::Char(' ') => self.change_focus(),
-                _ => {}
-            },
+            Event::Key(key_ev) if key_ev.kind == KeyEventKind::Press => {
+                if let KeyCode::Char(' ') = key_ev.code
+                    && !self.captures_input()
+                {
+                    self.change_focus()
+                }
+            }
 
             _ => {}
         }
@@ -64,26 +90,40 @@ where
         self.j.update();
     }
 
+    fn captures_input(&self) -> bool {
+        self.v.captures_input() || self.j.captures_input()
+    }
+
+    fn handles_key(&self, key: KeyCode) -> bool {
+        (self.v.is_focused() && self.v.handles_key(key))
+            || (self.j.is_focused() && self.j.handles_key(key))
+    }
+
     fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
-        self.v.render(
-            Rect::new(area.x, area.y, area.width * 3 / 4, area.height),
-            buf,
-        );
+        let (v_area, j_area) = Self::split(area);
+        Self::render_divider(area, buf);
 
-        render_vertical_line(
-            Rect::new(area.x + area.width * 3 / 4, area.y, 1, area.height),
-            buf,
-        );
+        self.v.render(v_area, buf);
+        self.j.render(j_area, buf);
+    }
+}
 
-        self.j.render(
-            Rect::new(
-                area.x + area.width * 3 / 4 + 1,
-                area.y,
-                area.width / 4,
-                area.height,
-            ),
-            buf,
-        );
+impl<V, J> ViewWithCursorControl for Layout<V, J>
+where
+    V: FocusableView + FocusStatus,
+    J: FocusableView + FocusStatus + ViewWithCursorControl,
+{
+    fn render_with_cursor(
+        &self,
+        area: Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        set_cursor: &mut dyn FnMut(u16, u16),
+    ) {
+        let (v_area, j_area) = Self::split(area);
+        Self::render_divider(area, buf);
+
+        self.v.render(v_area, buf);
+        self.j.render_with_cursor(j_area, buf, set_cursor);
     }
 }
 
@@ -141,7 +181,7 @@ where
 
 fn render_vertical_line(area: Rect, buf: &mut ratatui::prelude::Buffer) {
     let height = area.height as usize;
-    let vertical_line: String = std::iter::repeat("│\n").take(height).collect();
+    let vertical_line: String = std::iter::repeat_n("│\n", height).collect();
 
     let line_paragraph = Paragraph::new(vertical_line).block(Block::default());
 
@@ -1,8 +1,10 @@
+pub mod event_popup;
 pub mod ics_popup;
 pub mod input;
 pub mod input_popup;
 pub mod layout;
 pub mod popup_host;
 pub mod popup_with_cursor;
-pub mod tmp_popup;
+pub mod reminder_banner;
+pub mod text_area;
 pub mod view_switcher;
@@ -1,8 +1,6 @@
 use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind};
 
-use crate::ui::common::view::{
-    FocusableView, FocusableViewWithCursorControl, PopupView, View, ViewWithCursorControl,
-};
+use crate::ui::common::view::{FocusableView, PopupView, View, ViewWithCursorControl};
 
 pub struct PopupHost<V>
 where
@@ -51,7 +49,7 @@ where
         self.active_popup = Some(idx);
         self.inner.unfocus();
 
-        if let Ok(_) = self.popups[idx].reset() {
+        if self.popups[idx].reset().is_ok() {
             self.popups[idx].focus();
         }
     }
@@ -74,9 +72,9 @@ where
         }
     }
 
-    fn active_popup(&self) -> Option<&Box<dyn PopupView>> {
+    fn active_popup(&self) -> Option<&dyn PopupView> {
         if let Some(active_idx) = self.active_popup {
-            Some(&self.popups[active_idx])
+            Some(self.popups[active_idx].as_ref())
         } else {
             None
         }
@@ -86,14 +84,18 @@ where
 impl<V: FocusableView> View for PopupHost<V> {
     fn handle_event(&mut self, e: &ratatui::crossterm::event::Event) -> anyhow::Result<()> {
         match e {
+            // everything goes to the view typing into
+            _ if self.active_popup.is_none() && self.inner.captures_input() => {}
             Event::Key(key_ev) if key_ev.kind == KeyEventKind::Press => match key_ev.code {
                 KeyCode::Esc => self.hide(),
                 k => {
-                    if let None = self.active_popup {
-                        if let Some(idx) = self.popups_triggers.iter().position(|&x| x == k) {
-                            self.show(idx);
-                            return Ok(()); // if we open popup we won't pass events down for anyone
-                        }
+                    if self.active_popup.is_none()
+                        && !self.inner.handles_key(k)
+                        && let Some(idx) = self.popups_triggers.iter().position(|&x| x == k)
+                        && !self.popups[idx].is_empty()
+                    {
+                        self.show(idx);
+                        return Ok(()); // if we open popup we won't pass events down for anyone
                     }
                 }
             },
@@ -115,6 +117,13 @@ impl<V: FocusableView> View for PopupHost<V> {
         }
     }
 
+    fn captures_input(&self) -> bool {
+        match self.active_popup() {
+            Some(popup) => popup.captures_input(),
+            None => self.inner.captures_input(),
+        }
+    }
+
     fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
         self.inner.render(area, buf);
         if let Some(active) = self.active_popup() {
@@ -123,16 +132,102 @@ impl<V: FocusableView> View for PopupHost<V> {
     }
 }
 
-impl<V: FocusableView> ViewWithCursorControl for PopupHost<V> {
+impl<V: FocusableView + ViewWithCursorControl> ViewWithCursorControl for PopupHost<V> {
     fn render_with_cursor(
         &self,
         area: ratatui::prelude::Rect,
         buf: &mut ratatui::prelude::Buffer,
         set_cursor: &mut dyn FnMut(u16, u16),
     ) {
-        self.inner.render(area, buf);
-        if let Some(active) = self.active_popup() {
-            active.render_with_cursor(area, buf, set_cursor);
+        match self.active_popup() {
+            // cursor of the view below is covered by the popup
+            Some(active) => {
+                self.inner.render(area, buf);
+                active.render_with_cursor(area, buf, set_cursor);
+            }
+            None => self.inner.render_with_cursor(area, buf, set_cursor),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use chrono::NaiveDate;
+    use chrono_tz::Tz;
+    use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::{
+        common::index::CalendarIndex,
+        config::Config,
+        providers::gcal::parse_ics,
+        state::{AppState, State},
+        ui::{components::event_popup::new_event_popup, monthly::MonthlyView, tasks::TaskListView},
+    };
+
+    const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:meeting\r
+DTSTART:20251021T100000Z\r
+DTEND:20251021T110000Z\r
+SUMMARY:Meeting\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    fn state() -> AppState {
+        let mut state = State::new(Config {
+            timezone: Tz::UTC,
+            ..Config::default()
+        });
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 10, 21).unwrap();
+        state.set_calendar(CalendarIndex::new(
+            parse_ics(ICS.as_bytes()).unwrap(),
+            Tz::UTC,
+        ));
+        AppState::new(state, channel().0)
+    }
+
+    fn host<V: FocusableView>(view: V, state: &AppState) -> PopupHost<V> {
+        PopupHost::new(view).with_popups(
+            vec![Box::new(new_event_popup(state.clone()))],
+            vec![KeyCode::Enter],
+        )
+    }
+
+    fn press(host: &mut PopupHost<impl FocusableView>, code: KeyCode) {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        host.handle_event(&Event::Key(key)).unwrap();
+        host.update();
+    }
+
+    #[test]
+    fn details_open_only_for_a_selected_event() {
+        let state = state();
+        let mut monthly = host(MonthlyView::new(state.clone()), &state);
+        monthly.update();
+
+        // hidden cursor, no day and no event
+        press(&mut monthly, KeyCode::Enter);
+        assert_eq!(monthly.active_popup, None);
+
+        // the first event of the day is selected with the cursor on it
+        press(&mut monthly, KeyCode::Char('s'));
+        assert!(state.state.borrow().selected_event.is_some());
+        press(&mut monthly, KeyCode::Enter);
+        assert_eq!(monthly.active_popup, Some(0));
+    }
+
+    #[test]
+    fn tasks_keep_enter() {
+        let state = state();
+        let mut tasks = host(TaskListView::new(state.clone()), &state);
+        tasks.update();
+
+        press(&mut tasks, KeyCode::Enter);
+        assert_eq!(tasks.active_popup, None);
+    }
+}
@@ -65,7 +65,7 @@ impl<V> View for Popup<'_, V>
 where
     V: FocusableView + ViewWithCursorControl + Resettable,
 {
-    fn handle_event(&mut self, e: &Event) -> anyhow::Result<()> {
+    fn handle_event(&mut self, _e: &Event) -> anyhow::Result<()> {
         Ok(())
     }
 
@@ -73,6 +73,14 @@ where
         self.content.update();
     }
 
+    fn captures_input(&self) -> bool {
+        self.open && self.content.captures_input()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
     fn render(&self, area: Rect, buf: &mut Buffer) {
         if self.open {
             Clear.render(area, buf);
@@ -0,0 +1,106 @@
+use chrono::{Duration, Utc};
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{state::AppState, ui::common::view::View};
+
+// One-line notification of the oldest reminder not dismissed yet
+pub struct ReminderBanner {
+    state: AppState,
+}
+
+impl ReminderBanner {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.state.state.borrow().reminders.active().is_some()
+    }
+
+    /// Handles snooze (`z`) and dismiss (`x`), returns whether the key was used
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        if !self.is_visible() {
+            return false;
+        }
+
+        let mut state = self.state.state.borrow_mut();
+        match code {
+            KeyCode::Char('z') => {
+                let snooze = Duration::minutes(state.config.snooze_minutes as i64);
+                state.reminders.snooze(Utc::now(), snooze);
+            }
+            KeyCode::Char('x') => state.reminders.dismiss(),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl View for ReminderBanner {
+    fn handle_event(&mut self, e: &Event) -> anyhow::Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            self.handle_key(key_ev.code);
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) {}
+
+    fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let state = self.state.state.borrow();
+        let Some(reminder) = state.reminders.active() else {
+            return;
+        };
+        let tz = &state.config.timezone;
+
+        let event = &reminder.event;
+        let start = event.start_utc(tz);
+        let minutes = (start - Utc::now()).num_minutes();
+        let when = if event.is_all_day() {
+            format!("{}", event.start.in_zone(tz).format("%a %d %b"))
+        } else if minutes > 0 {
+            format!(
+                "{} (in {} min)",
+                start.with_timezone(tz).format("%H:%M"),
+                minutes
+            )
+        } else if minutes == 0 {
+            "now".to_string()
+        } else {
+            format!("started {} min ago", -minutes)
+        };
+
+        let mut spans = vec![
+            Span::from(" 🔔 "),
+            Span::from(event.summary.clone().unwrap_or_default()).bold(),
+            Span::from(format!("  {when}")),
+        ];
+        if let Some(text) = reminder
+            .text
+            .as_ref()
+            .filter(|t| Some(*t) != event.summary.as_ref())
+        {
+            spans.push(Span::from(format!("  {text}")).italic());
+        }
+        if state.reminders.pending() > 1 {
+            spans.push(Span::from(format!(
+                "  (+{} more)",
+                state.reminders.pending() - 1
+            )));
+        }
+        spans.push(Span::from("   z snooze · x dismiss").dim());
+
+        Paragraph::new(Line::from(spans))
+            .style(Style::default().bg(Color::Yellow).fg(Color::Black))
+            .render(area, buf);
+    }
+}
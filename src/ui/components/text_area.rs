@@ -0,0 +1,403 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::prelude::Widget;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::ui::common::focusable::{FocusStatus, Focusable};
+use crate::ui::common::view::{
+    FocusableView, FocusableViewWithCursorControl, Resettable, View, ViewWithCursorControl,
+};
+
+// Multi-line text editor, long lines are wrapped at word boundaries
+pub struct TextArea {
+    title: String,
+    borders: bool,
+
+    lines: Vec<String>,
+    // line and byte index in it, always on a grapheme boundary
+    row: usize,
+    col: usize,
+
+    focused: bool,
+
+    // set while rendering: first visible row and text width, used for scrolling and
+    // moving between wrapped rows
+    scroll: Cell<usize>,
+    width: Cell<usize>,
+}
+
+// one row on screen: part `start..end` (byte indices) of `line`
+#[derive(Clone, Copy)]
+struct VisualRow {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+impl TextArea {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            borders: true,
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            focused: true,
+            scroll: Cell::new(0),
+            width: Cell::new(0),
+        }
+    }
+
+    /// Renders just the text, for placing inside an existing block
+    pub fn without_borders(mut self) -> Self {
+        self.borders = false;
+        self
+    }
+
+    pub fn content(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Replaces the text, cursor goes to its end
+    pub fn set_content(&mut self, text: &str) {
+        self.lines = text.split('\n').map(str::to_string).collect();
+        self.row = self.lines.len() - 1;
+        self.col = self.line_len(self.row);
+        self.scroll.set(0);
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].len()
+    }
+
+    // start of the grapheme before the cursor
+    fn prev_boundary(&self) -> Option<usize> {
+        self.lines[self.row][..self.col]
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(i, _)| i)
+    }
+
+    // end of the grapheme after the cursor
+    fn next_boundary(&self) -> Option<usize> {
+        self.lines[self.row][self.col..]
+            .graphemes(true)
+            .next()
+            .map(|g| self.col + g.len())
+    }
+
+    fn insert(&mut self, c: char) {
+        let line = &mut self.lines[self.row];
+        line.insert(self.col, c);
+        self.col += c.len_utf8();
+
+        // typed before a combining mark the char becomes part of its grapheme,
+        // cursor goes after the whole of it
+        if let Some((i, g)) = line
+            .grapheme_indices(true)
+            .find(|(i, g)| *i < self.col && self.col < i + g.len())
+        {
+            self.col = i + g.len();
+        }
+    }
+
+    fn new_line(&mut self) {
+        let rest = self.lines[self.row].split_off(self.col);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        if let Some(prev) = self.prev_boundary() {
+            self.lines[self.row].replace_range(prev..self.col, "");
+            self.col = prev;
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if let Some(next) = self.next_boundary() {
+            self.lines[self.row].replace_range(self.col..next, "");
+        } else if self.row + 1 < self.lines.len() {
+            let line = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if let Some(prev) = self.prev_boundary() {
+            self.col = prev;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+        }
+    }
+
+    fn move_right(&mut self) {
+        if let Some(next) = self.next_boundary() {
+            self.col = next;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    // to the previous or next row on screen, keeping the display column where possible
+    fn move_vertically(&mut self, down: bool) {
+        let rows = self.visual_rows(self.width.get());
+        let (idx, x) = self.cursor_row(&rows);
+
+        let target_idx = if down {
+            idx + 1
+        } else if idx > 0 {
+            idx - 1
+        } else {
+            return;
+        };
+        let Some(target) = rows.get(target_idx) else {
+            return;
+        };
+        let text = &self.lines[target.line][target.start..target.end];
+
+        // last grapheme starting at or before the column
+        let mut col = target.start;
+        let mut width = 0;
+        for (i, g) in text.grapheme_indices(true) {
+            if width + g.width() > x {
+                break;
+            }
+            width += g.width();
+            col = target.start + i + g.len();
+        }
+
+        // end of a wrapped row is already the start of the next one
+        let last_in_line = rows
+            .get(target_idx + 1)
+            .is_none_or(|next| next.line != target.line);
+        if !last_in_line {
+            let last = text.grapheme_indices(true).next_back();
+            col = col.min(last.map_or(target.start, |(i, _)| target.start + i));
+        }
+
+        self.row = target.line;
+        self.col = col;
+    }
+
+    fn visual_rows(&self, width: usize) -> Vec<VisualRow> {
+        self.lines
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| {
+                wrap(line, width)
+                    .into_iter()
+                    .map(move |(start, end)| VisualRow {
+                        line: i,
+                        start,
+                        end,
+                    })
+            })
+            .collect()
+    }
+
+    // visual row of the cursor and display column in it
+    fn cursor_row(&self, rows: &[VisualRow]) -> (usize, usize) {
+        let idx = rows
+            .iter()
+            .rposition(|r| r.line == self.row && r.start <= self.col)
+            .unwrap_or_default();
+        let start = rows[idx].start;
+
+        (idx, self.lines[self.row][start..self.col].width())
+    }
+}
+
+// rows `(start, end)` a line is wrapped into by display width, breaking after spaces
+// where possible
+fn wrap(line: &str, width: usize) -> Vec<(usize, usize)> {
+    if width == 0 || line.width() <= width {
+        return vec![(0, line.len())];
+    }
+
+    let mut rows = vec![];
+    let mut start = 0;
+    let mut row_width = 0;
+    // end of the last space in the current row
+    let mut after_space = None;
+
+    for (i, g) in line.grapheme_indices(true) {
+        if row_width + g.width() > width && i > start {
+            let end = after_space.unwrap_or(i);
+            rows.push((start, end));
+            row_width = line[end..i].width();
+            start = end;
+            after_space = None;
+        }
+
+        row_width += g.width();
+        if g == " " {
+            after_space = Some(i + g.len());
+        }
+    }
+    rows.push((start, line.len()));
+
+    rows
+}
+
+impl View for TextArea {
+    fn handle_event(&mut self, e: &Event) -> Result<()> {
+        match e {
+            Event::Key(key_ev) if key_ev.kind == KeyEventKind::Press => match key_ev.code {
+                KeyCode::Char(c) => {
+                    if key_ev.modifiers.contains(KeyModifiers::CONTROL) {
+                        // ignore control + char input
+                    } else {
+                        self.insert(c);
+                    }
+                }
+                KeyCode::Enter => self.new_line(),
+                KeyCode::Backspace => self.backspace(),
+                KeyCode::Delete => self.delete(),
+                KeyCode::Left => self.move_left(),
+                KeyCode::Right => self.move_right(),
+                KeyCode::Up => self.move_vertically(false),
+                KeyCode::Down => self.move_vertically(true),
+                KeyCode::Home => self.col = 0,
+                KeyCode::End => self.col = self.line_len(self.row),
+                _ => {}
+            },
+            Event::Paste(text) => {
+                for c in text.chars() {
+                    match c {
+                        '\n' => self.new_line(),
+                        '\r' => {}
+                        c => self.insert(c),
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) {}
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.render_with_cursor(area, buf, &mut |_x, _y| {});
+    }
+}
+
+impl ViewWithCursorControl for TextArea {
+    fn render_with_cursor(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        set_cursor: &mut dyn FnMut(u16, u16),
+    ) {
+        let inner = if self.borders {
+            let block = Block::default()
+                .title(self.title.as_str())
+                .borders(Borders::ALL)
+                .border_style(Style::default());
+            let inner = block.inner(area);
+            block.render(area, buf);
+            inner
+        } else {
+            area
+        };
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let width = inner.width as usize;
+        let height = inner.height as usize;
+        self.width.set(width);
+
+        let rows = self.visual_rows(width);
+        let (cursor_idx, mut x) = self.cursor_row(&rows);
+        let mut y = cursor_idx;
+        // cursor after a full row goes to the start of the next one
+        if x >= width {
+            x = 0;
+            y += 1;
+        }
+
+        // scrolled just enough for the cursor to be visible
+        let scroll = self.scroll.get().min(y).max((y + 1).saturating_sub(height));
+        self.scroll.set(scroll);
+
+        let lines: Vec<Line> = rows
+            .iter()
+            .skip(scroll)
+            .take(height)
+            .map(|r| Line::from(&self.lines[r.line][r.start..r.end]))
+            .collect();
+
+        Paragraph::new(lines)
+            .style(Style::default())
+            .render(inner, buf);
+
+        if self.focused {
+            set_cursor(inner.x + x as u16, inner.y + (y - scroll) as u16);
+        }
+    }
+}
+
+impl Focusable for TextArea {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = !self.focused;
+    }
+}
+
+impl FocusableView for TextArea {
+    fn handle_event_if_focused(&mut self, e: &Event) -> Result<()> {
+        if self.focused {
+            self.handle_event(e)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl FocusableViewWithCursorControl for TextArea {}
+
+impl Resettable for TextArea {
+    fn reset(&mut self) -> Result<()> {
+        self.lines = vec![String::new()];
+        self.row = 0;
+        self.col = 0;
+        self.scroll.set(0);
+
+        Ok(())
+    }
+}
+
+impl FocusStatus for TextArea {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
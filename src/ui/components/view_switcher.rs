@@ -1,7 +1,4 @@
-use ratatui::{
-    crossterm::event::{Event, KeyCode, KeyEventKind},
-    widgets::Widget,
-};
+use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind};
 
 use crate::ui::common::{
     focusable::{FocusStatus, Focusable},
@@ -35,18 +32,24 @@ impl<'a> ViewSwitcher<'a> {
     }
 
     fn curr_view(&self) -> &dyn FocusableView {
-        return self.views[self.curr_view_idx];
+        self.views[self.curr_view_idx]
     }
 
     fn mut_curr_view(&mut self) -> &mut dyn FocusableView {
-        return self.views[self.curr_view_idx];
+        self.views[self.curr_view_idx]
     }
 
     fn next_view(&mut self) {
-        self.curr_view_idx += 1;
+        self.switch_to((self.curr_view_idx + 1) % self.views.len());
+    }
+
+    fn switch_to(&mut self, idx: usize) {
+        self.mut_curr_view().unfocus();
+
+        self.curr_view_idx = idx;
 
-        if self.curr_view_idx >= self.views.len() {
-            self.curr_view_idx = 0;
+        if self.focused {
+            self.mut_curr_view().focus();
         }
     }
 }
@@ -58,20 +61,35 @@ impl View for ViewSwitcher<'_> {
 
     fn handle_event(&mut self, e: &Event) -> Result<()> {
         // handle view switching
-        if let Event::Key(key_ev) = e {
-            if key_ev.kind == KeyEventKind::Press {
-                if let KeyCode::Char(v) = key_ev.code {
-                    if v == self.switch_char {
-                        self.next_view();
-                    }
-                }
-            }
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+            && let KeyCode::Char(v) = key_ev.code
+            && v == self.switch_char
+            && !self.captures_input()
+        {
+            self.next_view();
         }
         Ok(())
     }
 
     fn update(&mut self) {
         self.mut_curr_view().update();
+
+        // current view asked for another one
+        if let Some(kind) = self.mut_curr_view().take_switch_request()
+            && let Some(idx) = self.views.iter().position(|v| v.kind() == Some(kind))
+        {
+            self.switch_to(idx);
+            self.mut_curr_view().update();
+        }
+    }
+
+    fn captures_input(&self) -> bool {
+        self.curr_view().captures_input()
+    }
+
+    fn handles_key(&self, key: KeyCode) -> bool {
+        self.curr_view().handles_key(key)
     }
 }
 
@@ -1,41 +1,284 @@
+use std::cell::Cell;
+
+use chrono::{NaiveDate, NaiveTime, Utc};
 use ratatui::{
-    crossterm::event::Event,
-    style::Stylize,
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::Rect,
+    style::{Color, Style, Stylize},
     text::Line,
     widgets::{Block, Widget},
 };
 
 use anyhow::Result;
 
-use crate::ui::common::{
-    focusable::Focusable,
-    view::{FocusableView, View},
+use crate::{
+    state::AppState,
+    ui::common::{
+        focusable::Focusable,
+        timeline::{
+            ALL_DAY_LINES, AXIS_WIDTH, DayEvents, TimeGrid, render_all_day, render_axis,
+            render_events, render_now_line,
+        },
+        view::{FocusableView, View, ViewKind},
+    },
 };
 
+// Timeline of the selected day
 pub struct DailyView {
+    state: AppState,
     focused: bool,
+
+    // cursor row in the timeline, `None` when on the all-day strip
+    row: Option<usize>,
+    // which of the events under the cursor is selected, from the left
+    column: usize,
+    // time this view last selected, when the selection differs another view moved it
+    last_time: NaiveTime,
+
+    // first visible row, set while rendering
+    scroll: Cell<usize>,
 }
 
 impl DailyView {
-    pub fn new() -> Self {
-        Self { focused: false }
+    pub fn new(state: AppState) -> Self {
+        let (row, time) = {
+            let state = state.state.borrow();
+            let time = state.selected_time;
+            (TimeGrid::new(&state.config).row_at(time), time)
+        };
+
+        Self {
+            state,
+            focused: false,
+            row: Some(row),
+            column: 0,
+            last_time: time,
+            scroll: Cell::new(0),
+        }
+    }
+
+    fn day(&self) -> NaiveDate {
+        self.state.state.borrow().selected_date
+    }
+
+    fn grid(&self) -> TimeGrid {
+        TimeGrid::new(&self.state.state.borrow().config)
+    }
+
+    fn events(&self) -> DayEvents {
+        DayEvents::load(&self.state.state.borrow(), self.day())
+    }
+
+    fn move_down(&mut self) {
+        let rows = self.grid().rows();
+        self.row = Some(self.row.map_or(0, |r| (r + 1).min(rows.saturating_sub(1))));
+        self.column = 0;
+    }
+
+    fn move_up(&mut self, events: &DayEvents) {
+        self.row = match self.row {
+            Some(0) if !events.all_day.is_empty() => None,
+            Some(r) => Some(r.saturating_sub(1)),
+            None => None,
+        };
+        self.column = 0;
+    }
+
+    // next or previous day, the other views follow the selected date
+    fn move_day(&mut self, forward: bool) {
+        let mut state = self.state.state.borrow_mut();
+        let day = if forward {
+            state.selected_date.succ_opt()
+        } else {
+            state.selected_date.pred_opt()
+        };
+
+        if let Some(day) = day {
+            state.selected_date = day;
+        }
+        self.column = 0;
     }
 }
 
 impl View for DailyView {
-    fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
-        let title = Line::from("DAILY VIEW".bold());
+    fn kind(&self) -> Option<ViewKind> {
+        Some(ViewKind::Daily)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let day = self.day();
+        let grid = self.grid();
+        let events = self.events();
+
+        let title = Line::from(day.format("%A %d %B %Y").to_string().bold());
 
         let block = Block::new().title(title.centered());
+        let mut inner_area = block.inner(area);
+
+        // little offset
+        inner_area.y += 1;
+        inner_area.height = inner_area.height.saturating_sub(1);
 
         block.render(area, buf);
+
+        // all-day events and a line under them
+        if !events.all_day.is_empty() {
+            let height = events.all_day.len().min(ALL_DAY_LINES) as u16;
+            let strip = Rect::new(
+                inner_area.x + AXIS_WIDTH,
+                inner_area.y,
+                inner_area.width.saturating_sub(AXIS_WIDTH),
+                height.min(inner_area.height),
+            );
+            let selected = match self.row {
+                None if self.focused => events.at(None, self.column),
+                _ => None,
+            };
+            render_all_day(&events.all_day, strip, selected, buf);
+
+            let line_y = inner_area.y + height;
+            if line_y < inner_area.y + inner_area.height {
+                buf.set_string(
+                    inner_area.x,
+                    line_y,
+                    "─".repeat(inner_area.width as usize),
+                    Style::new().dim(),
+                );
+            }
+
+            inner_area.y += height + 1;
+            inner_area.height = inner_area.height.saturating_sub(height + 1);
+        }
+
+        let axis_area = Rect {
+            width: AXIS_WIDTH.min(inner_area.width),
+            ..inner_area
+        };
+        let events_area = Rect::new(
+            inner_area.x + axis_area.width,
+            inner_area.y,
+            inner_area.width - axis_area.width,
+            inner_area.height,
+        );
+
+        // scrolled just enough for the cursor to be visible
+        let height = inner_area.height as usize;
+        let cursor = self.row.unwrap_or_default();
+        let scroll = self
+            .scroll
+            .get()
+            .min(cursor)
+            .max((cursor + 1).saturating_sub(height))
+            .min(grid.rows().saturating_sub(height));
+        self.scroll.set(scroll);
+
+        if self.focused
+            && let Some(y) = self.row.and_then(|r| r.checked_sub(scroll))
+            && y < height
+        {
+            let line = Rect::new(
+                events_area.x,
+                events_area.y + y as u16,
+                events_area.width,
+                1,
+            );
+            buf.set_style(line, Style::new().bg(Color::DarkGray));
+        }
+
+        let (now_row, now_label) = {
+            let state = self.state.state.borrow();
+            let tz = &state.config.timezone;
+            let now = Utc::now();
+            (
+                grid.now_row(day, now, tz),
+                now.with_timezone(tz).format("%H:%M").to_string(),
+            )
+        };
+        render_now_line(events_area, scroll, now_row, buf);
+
+        let selected = match self.row {
+            Some(_) if self.focused => events.at(self.row, self.column),
+            _ => None,
+        };
+        let tz = self.state.state.borrow().config.timezone;
+        render_events(
+            &events.timed,
+            &events.boxes,
+            events_area,
+            scroll,
+            selected,
+            &tz,
+            buf,
+        );
+
+        render_axis(
+            &grid,
+            axis_area,
+            scroll,
+            now_row.map(|r| (r, now_label)),
+            buf,
+        );
     }
 
     fn handle_event(&mut self, e: &Event) -> Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            let events = self.events();
+
+            match key_ev.code {
+                KeyCode::Char('n') => self.move_day(true),
+                KeyCode::Char('p') => self.move_day(false),
+
+                KeyCode::Char('j') | KeyCode::Down => self.move_down(),
+                KeyCode::Char('k') | KeyCode::Up => self.move_up(&events),
+                KeyCode::Char('h') | KeyCode::Left => self.column = self.column.saturating_sub(1),
+                KeyCode::Char('l') | KeyCode::Right => {
+                    let under = events.under(self.row).len();
+                    self.column = (self.column + 1).min(under.saturating_sub(1));
+                }
+                KeyCode::Char(c @ ('J' | 'K')) => {
+                    if let Some((row, column)) = events.jump(self.row, c == 'J') {
+                        self.row = Some(row);
+                        self.column = column;
+                    }
+                }
+                _ => {}
+            }
+        }
+
         Ok(())
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self) {
+        let grid = self.grid();
+        let time = self.state.state.borrow().selected_time;
+        if time != self.last_time {
+            self.row = Some(grid.row_at(time));
+        }
+
+        let events = self.events();
+
+        // strip is gone when the day has no all-day events
+        if self.row.is_none() && events.all_day.is_empty() {
+            self.row = Some(0);
+        }
+        if let Some(row) = self.row {
+            self.row = Some(row.min(grid.rows().saturating_sub(1)));
+        }
+        self.column = self
+            .column
+            .min(events.under(self.row).len().saturating_sub(1));
+
+        let mut state = self.state.state.borrow_mut();
+        state.selected_event = events.event_at(self.row, self.column).cloned();
+        // the all-day strip keeps the time selected before
+        if let Some(row) = self.row {
+            state.selected_time = grid.time_of(row);
+        }
+        self.last_time = state.selected_time;
+    }
 }
 
 impl Focusable for DailyView {
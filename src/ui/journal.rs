@@ -1,39 +1,313 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
 use ratatui::{
-    crossterm::event::Event,
-    layout::Alignment,
-    widgets::{Block, Widget},
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Paragraph, Widget, Wrap},
 };
 
-use crate::ui::common::{
-    focusable::{FocusStatus, Focusable},
-    styles::title_style,
-    view::{FocusableView, View},
+use crate::{
+    common::{
+        calendar::{Calendar, CalendarEvent},
+        journal::JournalEntry,
+        time::EventTime,
+    },
+    providers::gcal::{export_gcal, import_gcal},
+    state::AppState,
+    ui::{
+        common::{
+            focusable::{FocusStatus, Focusable},
+            styles::title_style,
+            utils::{event_color, events_on_day, status_style},
+            view::{FocusableView, Resettable, View, ViewWithCursorControl},
+        },
+        components::text_area::TextArea,
+    },
 };
 
+// Events and journal entry of the day selected in the monthly view, the entry is kept as
+// VJOURNAL in a local file
 pub struct Journal {
+    state: AppState,
     focused: bool,
+
+    path: Option<PathBuf>,
+    // contents of the journal file, `None` if it could not be read
+    calendar: Option<Calendar>,
+    error: Option<String>,
+
+    // day of the entry being edited
+    editing: Option<NaiveDate>,
+    editor: TextArea,
 }
 
 impl Journal {
-    pub fn new() -> Self {
-        Self { focused: false }
+    pub fn new(state: AppState) -> Self {
+        let path = state.state.borrow().config.journal_path.clone();
+
+        let mut journal = Self {
+            state,
+            focused: false,
+            path,
+            calendar: None,
+            error: None,
+            editing: None,
+            editor: TextArea::new("").without_borders(),
+        };
+
+        match journal.load() {
+            Ok(calendar) => journal.calendar = Some(calendar),
+            Err(e) => journal.error = Some(format!("{e:#}")),
+        }
+
+        journal
+    }
+
+    fn load(&self) -> Result<Calendar> {
+        match &self.path {
+            Some(path) if path.exists() => import_gcal(path.clone())
+                .with_context(|| format!("Failed to read {}", path.display())),
+            _ => Ok(Calendar::from_events("Journal".to_string(), vec![])),
+        }
+    }
+
+    fn entry(&self, day: NaiveDate) -> Option<&JournalEntry> {
+        let tz = &self.state.state.borrow().config.timezone;
+        self.calendar
+            .as_ref()?
+            .journals
+            .iter()
+            .find(|e| e.day(tz) == day)
+    }
+
+    // events of the day, `10:00-11:00 Summary @ Location` each
+    fn event_lines(&self, day: NaiveDate) -> Vec<Line<'static>> {
+        let state = self.state.state.borrow();
+        let Some(index) = &state.calendar else {
+            return vec![];
+        };
+        let tz = &state.config.timezone;
+
+        let line = |e: &CalendarEvent| {
+            let time = if e.is_all_day() {
+                format!("{:<11} ", "all day")
+            } else {
+                format!(
+                    "{}-{} ",
+                    e.start.in_zone(tz).format("%H:%M"),
+                    e.end.in_zone(tz).format("%H:%M")
+                )
+            };
+            let color = event_color(e, index.calendar());
+
+            let mut spans = vec![
+                Span::styled(time, Style::reset().fg(color)),
+                Span::styled(
+                    e.summary.clone().unwrap_or_default(),
+                    Style::reset().patch(status_style(&e.status)),
+                ),
+            ];
+            if let Some(location) = &e.location {
+                spans.push(Span::styled(format!(" @ {location}"), Style::reset().dim()));
+            }
+
+            Line::from(spans)
+        };
+
+        events_on_day(index, day, &state.config)
+            .iter()
+            .map(line)
+            .collect()
+    }
+
+    fn start_editing(&mut self) {
+        // a file we failed to read is never overwritten
+        if self.calendar.is_none() || self.path.is_none() {
+            return;
+        }
+
+        let day = self.state.state.borrow().selected_date;
+        let text = self
+            .entry(day)
+            .map(|e| e.description.clone())
+            .unwrap_or_default();
+
+        self.editor.set_content(&text);
+        self.editing = Some(day);
+    }
+
+    fn finish_editing(&mut self) {
+        let Some(day) = self.editing.take() else {
+            return;
+        };
+
+        let text = self.editor.content();
+        self.error = self.store(day, text).err().map(|e| format!("{e:#}"));
+        // text area never fails to reset
+        let _ = self.editor.reset();
+    }
+
+    // puts the entry into the journal and writes the file, empty text removes the entry
+    fn store(&mut self, day: NaiveDate, text: String) -> Result<()> {
+        let (Some(calendar), Some(path)) = (&mut self.calendar, &self.path) else {
+            return Ok(());
+        };
+        let tz = self.state.state.borrow().config.timezone;
+
+        let idx = calendar.journals.iter().position(|e| e.day(&tz) == day);
+        match (idx, text.trim().is_empty()) {
+            (Some(idx), true) => {
+                calendar.journals.remove(idx);
+            }
+            // a changed entry is stamped when written
+            (Some(idx), false) => {
+                let entry = &mut calendar.journals[idx];
+                if entry.description != text {
+                    entry.description = text;
+                    entry.dtstamp = None;
+                }
+            }
+            (None, true) => return Ok(()),
+            (None, false) => calendar.journals.push(JournalEntry {
+                uid: format!(
+                    "{}-{}@taskrs",
+                    day.format("%Y%m%d"),
+                    Utc::now().timestamp_millis()
+                ),
+                start: EventTime::Date(day),
+                summary: None,
+                description: text,
+                dtstamp: None,
+                extra: vec![],
+            }),
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        export_gcal(calendar, path.clone())
+            .with_context(|| format!("Failed to write {}", path.display()))
     }
 }
 
 impl View for Journal {
-    fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
-        let block = Block::new()
-            .title("JOURNAL")
-            .title_alignment(Alignment::Center)
-            .style(title_style(self.focused));
-        block.render(area, buf);
+    fn render(&self, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        self.render_with_cursor(area, buf, &mut |_x, _y| {});
     }
 
-    fn handle_event(&mut self, e: &Event) -> anyhow::Result<()> {
+    fn handle_event(&mut self, e: &Event) -> Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            match key_ev.code {
+                KeyCode::Esc if self.editing.is_some() => {
+                    self.finish_editing();
+                    return Ok(());
+                }
+                KeyCode::Char('e') if self.editing.is_none() => {
+                    self.start_editing();
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        if self.editing.is_some() {
+            self.editor.handle_event(e)?;
+        }
+
         Ok(())
     }
 
     fn update(&mut self) {}
+
+    fn captures_input(&self) -> bool {
+        self.editing.is_some()
+    }
+}
+
+impl ViewWithCursorControl for Journal {
+    fn render_with_cursor(
+        &self,
+        area: Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        set_cursor: &mut dyn FnMut(u16, u16),
+    ) {
+        let day = self
+            .editing
+            .unwrap_or_else(|| self.state.state.borrow().selected_date);
+
+        let block = Block::new()
+            .title(format!("JOURNAL {}", day.format("%a %d %b")))
+            .title_alignment(Alignment::Center)
+            .style(title_style(self.focused));
+        let mut inner_area = block.inner(area);
+        block.render(area, buf);
+
+        // little offset, last line is for hints
+        inner_area.y += 1;
+        inner_area.height = inner_area.height.saturating_sub(2);
+
+        // events first, leaving a few lines for the entry, then a blank line
+        let events = self.event_lines(day);
+        if !events.is_empty() {
+            let height = (events.len() as u16).min(
+                inner_area
+                    .height
+                    .saturating_sub(4)
+                    .max(inner_area.height / 2),
+            );
+            Paragraph::new(events).style(Style::reset()).render(
+                Rect {
+                    height,
+                    ..inner_area
+                },
+                buf,
+            );
+
+            inner_area.y += height + 1;
+            inner_area.height = inner_area.height.saturating_sub(height + 1);
+        }
+
+        if self.editing.is_some() {
+            buf.set_style(inner_area, Style::reset());
+            self.editor.render_with_cursor(inner_area, buf, set_cursor);
+        } else {
+            let text = match self.entry(day) {
+                Some(entry) => Text::from(entry.description.as_str()),
+                None => Text::from("No entry".dim()),
+            };
+
+            Paragraph::new(text)
+                .style(Style::reset())
+                .wrap(Wrap { trim: false })
+                .render(inner_area, buf);
+        }
+
+        let status = if let Some(err) = &self.error {
+            Line::styled(err.clone(), Style::reset().fg(Color::Red))
+        } else if self.editing.is_some() {
+            Line::styled("Esc save", Style::reset().dim())
+        } else if self.focused && self.path.is_none() {
+            // nowhere to save entries, editing is off
+            Line::styled(
+                "no journal file, set `journal` in the config to write entries",
+                Style::reset().fg(Color::Yellow),
+            )
+        } else if self.focused {
+            Line::styled("e edit", Style::reset().dim())
+        } else {
+            Line::default()
+        };
+        let status_y = inner_area.y + inner_area.height;
+        if status_y < area.y + area.height {
+            status.render(Rect::new(inner_area.x, status_y, inner_area.width, 1), buf);
+        }
+    }
 }
 
 impl Focusable for Journal {
@@ -43,15 +317,20 @@ impl Focusable for Journal {
 
     fn unfocus(&mut self) {
         self.focused = false;
+        self.finish_editing();
     }
 
     fn toggle_focus(&mut self) {
-        self.focused = !self.focused;
+        if self.focused {
+            self.unfocus();
+        } else {
+            self.focus();
+        }
     }
 }
 
 impl FocusableView for Journal {
-    fn handle_event_if_focused(&mut self, e: &Event) -> anyhow::Result<()> {
+    fn handle_event_if_focused(&mut self, e: &Event) -> Result<()> {
         if self.focused {
             self.handle_event(e)
         } else {
@@ -65,3 +344,39 @@ impl FocusStatus for Journal {
         self.focused
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use ratatui::{
+        buffer::Buffer,
+        crossterm::event::{KeyEvent, KeyModifiers},
+    };
+
+    use super::*;
+    use crate::{config::Config, state::State};
+
+    #[test]
+    fn editing_needs_a_journal_file() {
+        let state = State::new(Config {
+            journal_path: None,
+            ..Config::default()
+        });
+        let mut journal = Journal::new(AppState::new(state, channel().0));
+        journal.focus();
+
+        let key = KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE);
+        journal.handle_event(&Event::Key(key)).unwrap();
+        assert!(journal.editing.is_none());
+
+        // the hint line tells why
+        let area = Rect::new(0, 0, 80, 10);
+        let mut buf = Buffer::empty(area);
+        journal.render(area, &mut buf);
+        let status: String = (0..area.width)
+            .map(|x| buf[(x, area.height - 1)].symbol())
+            .collect();
+        assert!(status.contains("no journal file"), "{status:?}");
+    }
+}
@@ -1,5 +1,9 @@
+pub mod agenda;
 pub mod common;
 pub mod components;
 pub mod daily;
 pub mod journal;
 pub mod monthly;
+pub mod tasks;
+pub mod weekly;
+pub mod yearly;
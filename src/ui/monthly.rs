@@ -1,4 +1,4 @@
-use chrono::{Datelike, Local};
+use chrono::{Datelike, Days, Months, NaiveDate};
 use ratatui::{
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind},
     layout::Alignment,
@@ -9,20 +9,25 @@ use ratatui::{
 
 use anyhow::Result;
 
-use crate::ui::{
-    common::{
-        focusable::Focusable,
-        styles::title_style,
-        utils::month_info,
-        view::{FocusableView, View},
+use crate::{
+    state::AppState,
+    ui::{
+        common::{
+            focusable::Focusable,
+            styles::title_style,
+            utils::{events_on_day, month_info},
+            view::{FocusableView, View, ViewKind},
+        },
+        monthly::cursor::Cursor,
     },
-    monthly::cursor::Cursor,
 };
 
 mod cursor;
 mod render;
 
 pub struct MonthlyView {
+    state: AppState,
+
     curr_year: i32,
     curr_month: u32,
 
@@ -32,24 +37,48 @@ pub struct MonthlyView {
     focused: bool,
 
     c: Cursor,
+    // event of the cursor day picked with J/K, in order of start; the first one by default
+    event: Option<usize>,
+    // day this view last selected, when the selection differs another view moved it
+    last_date: NaiveDate,
 }
 
 impl MonthlyView {
-    pub fn new() -> Self {
-        let now = Local::now();
+    pub fn new(state: AppState) -> Self {
+        let selected = state.state.borrow().selected_date;
         Self {
-            curr_month: now.month(),
-            curr_year: now.year(),
+            state,
+            curr_month: selected.month(),
+            curr_year: selected.year(),
             c: Cursor::new().with_w(7).with_h(6),
             first_day: 0,
             month_len: 0,
             focused: false,
+            event: None,
+            last_date: selected,
         }
     }
 
+    // month of given day, with the cursor on it
+    fn show_date(&mut self, date: NaiveDate) {
+        self.curr_year = date.year();
+        self.curr_month = date.month();
+        (self.first_day, self.month_len) = month_info(self.curr_year, self.curr_month);
+
+        self.c.set_max_day(self.first_day + self.month_len - 1);
+        self.c.show_day(self.first_day + date.day0() as i32);
+    }
+
     fn handle_key_press_ev(&mut self, key_ev: &KeyEvent) -> Result<()> {
         // TODO: move keys to cfg
         match key_ev.code {
+            // the cursor day moves along, clamped to the end of the month
+            KeyCode::Char('n') if let Some(date) = self.cursor_date() => {
+                self.show_date(date.checked_add_months(Months::new(1)).unwrap_or(date))
+            }
+            KeyCode::Char('p') if let Some(date) = self.cursor_date() => {
+                self.show_date(date.checked_sub_months(Months::new(1)).unwrap_or(date))
+            }
             KeyCode::Char('n') => {
                 self.curr_month += 1;
                 if self.curr_month > 12 {
@@ -65,10 +94,16 @@ impl MonthlyView {
                     self.curr_month -= 1;
                 }
             }
+            // a shown cursor starts on the selected day
+            KeyCode::Char('s') if !self.c.is_shown() => self.show_date(self.last_date),
             KeyCode::Char('s') => {
                 self.c.change_shown();
             }
 
+            // clamped to the events of the day on update
+            KeyCode::Char('J') => self.event = Some(self.event.map_or(0, |i| i + 1)),
+            KeyCode::Char('K') => self.event = self.event.and_then(|i| i.checked_sub(1)),
+
             KeyCode::Char('h')
             | KeyCode::Char('j')
             | KeyCode::Char('k')
@@ -84,6 +119,17 @@ impl MonthlyView {
         Ok(())
     }
 
+    // day under the cursor, if it is shown and on a day of the month
+    fn cursor_date(&self) -> Option<NaiveDate> {
+        let day_num = self.c.current_day()? - self.first_day;
+        if day_num < 0 || day_num >= self.month_len {
+            return None;
+        }
+
+        NaiveDate::from_ymd_opt(self.curr_year, self.curr_month, 1)?
+            .checked_add_days(Days::new(day_num as u64))
+    }
+
     fn handle_movement_keys(&mut self, code: KeyCode) -> Result<()> {
         match code {
             KeyCode::Char('h') | KeyCode::Left => self.c.move_left(),
@@ -99,12 +145,43 @@ impl MonthlyView {
 }
 
 impl View for MonthlyView {
+    fn kind(&self) -> Option<ViewKind> {
+        Some(ViewKind::Monthly)
+    }
+
     fn update(&mut self) {
+        let selected = self.state.state.borrow().selected_date;
+        if selected != self.last_date {
+            self.show_date(selected);
+        }
+
         let (first_day_idx, days_in_month) = month_info(self.curr_year, self.curr_month);
         self.first_day = first_day_idx;
         self.month_len = days_in_month;
 
-        self.c.set_max_day(self.month_len);
+        // cursor cells are counted from the first one of the grid
+        self.c.set_max_day(self.first_day + self.month_len - 1);
+
+        let date = self.cursor_date();
+        // picked event is of the day the cursor was on
+        if date != Some(self.last_date) {
+            self.event = None;
+        }
+
+        let mut state = self.state.state.borrow_mut();
+        let events = match (&state.calendar, date) {
+            (Some(index), Some(date)) => events_on_day(index, date, &state.config),
+            _ => vec![],
+        };
+        // the first one unless another was picked
+        self.event = (!events.is_empty()).then(|| self.event.unwrap_or(0).min(events.len() - 1));
+        state.selected_event = self.event.and_then(|i| events.get(i).cloned());
+
+        // a hidden cursor keeps the selected day
+        if let Some(date) = date {
+            state.selected_date = date;
+        }
+        self.last_date = state.selected_date;
     }
 
     fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
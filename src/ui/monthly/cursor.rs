@@ -22,16 +22,19 @@ impl Cursor {
         }
     }
 
-    pub fn set_w(&mut self, w: i32) {
-        self.w = w;
+    pub(super) fn set_max_day(&mut self, max_day: i32) {
+        self.maxx = max_day;
     }
 
-    pub(super) fn set_h(&mut self, h: i32) {
-        self.h = h;
+    // shows cursor on given cell, counted from 0
+    pub(super) fn show_day(&mut self, day: i32) {
+        self.shown = true;
+        self.y = day / self.w;
+        self.x = day % self.w;
     }
 
-    pub(super) fn set_max_day(&mut self, max_day: i32) {
-        self.maxx = max_day;
+    pub(super) fn is_shown(&self) -> bool {
+        self.shown
     }
 
     pub(super) fn change_shown(&mut self) {
@@ -48,11 +51,6 @@ impl Cursor {
         self
     }
 
-    pub(super) fn with_max_day(mut self, max_day: i32) -> Self {
-        self.maxx = max_day;
-        self
-    }
-
     pub(super) fn move_left(&mut self) {
         if !self.shown {
             return;
@@ -102,11 +100,10 @@ impl Cursor {
     }
 
     fn clamp_to_maxx(&mut self) {
-        let day = self.y * self.w + self.x + 1;
+        let day = self.y * self.w + self.x;
         if day > self.maxx {
-            let new_day = self.maxx - 1;
-            self.y = new_day / self.w;
-            self.x = new_day % self.w;
+            self.y = self.maxx / self.w;
+            self.x = self.maxx % self.w;
         }
     }
 
@@ -115,8 +112,8 @@ impl Cursor {
             return None;
         }
 
-        let day = self.y * self.w + self.x + 1;
-        if day > 0 && day <= self.maxx {
+        let day = self.y * self.w + self.x;
+        if day >= 0 && day <= self.maxx {
             Some(day)
         } else {
             None
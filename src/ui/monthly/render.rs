@@ -1,11 +1,22 @@
+use chrono::{Days, NaiveDate};
+use chrono_tz::Tz;
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
-    style::{Color, Style},
-    text::Text,
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
     widgets::{Block, Paragraph, Widget},
 };
 
-use crate::ui::monthly::MonthlyView;
+use crate::{
+    common::{
+        calendar::{Calendar, CalendarEvent},
+        task::{Task, TaskStatus},
+    },
+    ui::{
+        common::utils::{event_color, events_between, status_style, truncate},
+        monthly::MonthlyView,
+    },
+};
 
 impl MonthlyView {
     fn render_days_titles(&self, inner_area: Rect, buf: &mut ratatui::prelude::Buffer) {
@@ -80,15 +91,88 @@ impl MonthlyView {
             .flat_map(|&row| horizontal.split(row).to_vec())
             .collect();
 
+        let state = self.state.state.borrow();
+        let tz = &state.config.timezone;
+        let calendar = state.calendar.as_ref().map(|c| c.calendar());
+
+        // event picked with J/K, shown reversed
+        let is_selected = |e: &CalendarEvent| {
+            self.focused
+                && state
+                    .selected_event
+                    .as_ref()
+                    .is_some_and(|s| s.uid == e.uid && s.start == e.start)
+        };
+
+        let Some(month_first) = NaiveDate::from_ymd_opt(self.curr_year, self.curr_month, 1) else {
+            return;
+        };
+        let month_last = month_first + Days::new(self.month_len.max(1) as u64 - 1);
+
+        let events = state
+            .calendar
+            .as_ref()
+            .map(|c| events_between(c, month_first, month_last, &state.config))
+            .unwrap_or_default();
+
+        // all-day and multi-day events are drawn as bars across cells
+        let (spanning, single): (Vec<&CalendarEvent>, Vec<&CalendarEvent>) =
+            events.iter().partition(|e| {
+                let (first, last) = e.days(tz);
+                e.is_all_day() || first != last
+            });
+
+        // tasks are shown on the day they are due
+        let tasks: Vec<(NaiveDate, &Task)> = state
+            .calendar
+            .iter()
+            .flat_map(|c| &c.calendar().tasks)
+            .filter(|t| !(state.config.hide_cancelled && t.status == TaskStatus::Cancelled))
+            .filter_map(|t| Some((t.due_day(tz)?, t)))
+            .filter(|(day, _)| (month_first..=month_last).contains(day))
+            .collect();
+
+        let cell_idx =
+            |d: NaiveDate| (self.first_day as i64 + (d - month_first).num_days()) as usize;
+        let segments = span_segments(&spanning, tz, month_first, month_last, cell_idx);
+
+        let lanes_in_row = shown_lanes(&segments, row_height as usize);
+
         for (i, cell) in cells.iter().enumerate() {
             let day_num = (i as i32) - self.first_day;
 
             // hide days not in curr month
-            let text = if day_num >= 0 && day_num < self.month_len {
-                format!("{}", day_num + 1)
-            } else {
-                "".to_string()
-            };
+            let mut lines = vec![];
+            if day_num >= 0 && day_num < self.month_len {
+                lines.push(Line::from(format!("{}", day_num + 1)));
+                lines.extend((0..lanes_in_row[i / 7]).map(|_| Line::default()));
+
+                let hidden = hidden_bars(&segments, &lanes_in_row, i);
+
+                let date = month_first + Days::new(day_num as u64);
+                let mut items: Vec<Line> = single
+                    .iter()
+                    .filter(|e| e.days(tz).0 == date)
+                    .map(|e| {
+                        let line = event_line(e, calendar, tz, cell.width as usize);
+                        if is_selected(e) {
+                            line.reversed()
+                        } else {
+                            line
+                        }
+                    })
+                    .collect();
+                items.extend(
+                    tasks
+                        .iter()
+                        .filter(|(day, _)| *day == date)
+                        .map(|(_, t)| task_line(t)),
+                );
+
+                let free = (cell.height as usize).saturating_sub(lines.len());
+                fit_items(&mut items, free, hidden);
+                lines.extend(items);
+            }
 
             // make weekdays red
             let weekday = i % 7;
@@ -101,11 +185,308 @@ impl MonthlyView {
                 Style::default()
             };
 
-            Paragraph::new(text)
+            Paragraph::new(lines)
                 .style(style)
                 .block(Block::new())
                 .alignment(Alignment::Left)
                 .render(*cell, buf);
         }
+
+        for seg in &segments {
+            let first = cells[seg.row * 7 + seg.first_col];
+            let last = cells[seg.row * 7 + seg.last_col];
+
+            if seg.lane >= lanes_in_row[seg.row] {
+                continue;
+            }
+
+            let area = Rect::new(
+                first.x,
+                first.y + 1 + seg.lane as u16,
+                last.x + last.width - first.x,
+                1,
+            );
+            let style = Style::default()
+                .bg(calendar.map_or(Color::Yellow, |c| event_color(seg.event, c)))
+                .fg(Color::Black)
+                .patch(status_style(&seg.event.status));
+            let style = if is_selected(seg.event) {
+                style.reversed()
+            } else {
+                style
+            };
+            let summary = seg.event.summary.as_deref().unwrap_or_default();
+            let text = if seg.continued {
+                format!("◂ {summary}")
+            } else {
+                summary.to_string()
+            };
+
+            buf.set_style(area, style);
+            buf.set_stringn(area.x, area.y, text, area.width as usize, style);
+        }
+    }
+}
+
+// `10:00 Summary` cut to the cell width, in the color of its calendar
+fn event_line(
+    event: &CalendarEvent,
+    calendar: Option<&Calendar>,
+    tz: &Tz,
+    width: usize,
+) -> Line<'static> {
+    let time = format!("{} ", event.start.in_zone(tz).format("%H:%M"));
+    let summary = truncate(
+        event.summary.as_deref().unwrap_or_default(),
+        width.saturating_sub(time.len()),
+    );
+    let color = calendar.map_or(Color::Yellow, |c| event_color(event, c));
+
+    Line::from(vec![
+        Span::styled(time, Style::new().fg(color).dim()),
+        Span::styled(
+            summary,
+            Style::new().fg(color).patch(status_style(&event.status)),
+        ),
+    ])
+}
+
+// `☐ Summary`, done and cancelled tasks are struck-through
+fn task_line(task: &Task) -> Line<'static> {
+    let (mark, style) = if task.is_done() {
+        ("☑ ", Style::new().crossed_out().dim())
+    } else if task.status == TaskStatus::Cancelled {
+        ("☐ ", Style::new().crossed_out().dim())
+    } else {
+        ("☐ ", Style::new())
+    };
+
+    Line::styled(
+        format!("{mark}{}", task.summary.as_deref().unwrap_or_default()),
+        style.fg(Color::Magenta),
+    )
+}
+
+// part of a spanning event inside one week row
+struct Segment<'a> {
+    event: &'a CalendarEvent,
+    row: usize,
+    first_col: usize,
+    last_col: usize,
+    lane: usize,
+    // event started before this segment
+    continued: bool,
+}
+
+fn span_segments<'a>(
+    events: &[&'a CalendarEvent],
+    tz: &Tz,
+    month_first: NaiveDate,
+    month_last: NaiveDate,
+    cell_idx: impl Fn(NaiveDate) -> usize,
+) -> Vec<Segment<'a>> {
+    let mut segments = vec![];
+
+    for event in events {
+        let (first, last) = event.days(tz);
+        let (first_idx, last_idx) = (
+            cell_idx(first.max(month_first)),
+            cell_idx(last.min(month_last)),
+        );
+
+        for row in first_idx / 7..=last_idx / 7 {
+            let from = first_idx.max(row * 7);
+            segments.push(Segment {
+                event,
+                row,
+                first_col: from % 7,
+                last_col: last_idx.min(row * 7 + 6) % 7,
+                lane: 0,
+                continued: from != first_idx || first < month_first,
+            });
+        }
+    }
+
+    // greedy lane assignment, longer segments first
+    segments.sort_by_key(|s| (s.row, s.first_col, std::cmp::Reverse(s.last_col)));
+
+    let mut lanes: Vec<(usize, usize)> = vec![]; // (row, last taken col) per lane
+    for seg in segments.iter_mut() {
+        let lane = lanes
+            .iter()
+            .position(|&(row, col)| row != seg.row || col < seg.first_col);
+
+        let lane = match lane {
+            Some(lane) => lane,
+            None => {
+                lanes.push((seg.row, 0));
+                lanes.len() - 1
+            }
+        };
+
+        lanes[lane] = (seg.row, seg.last_col);
+        seg.lane = lane;
+    }
+
+    segments
+}
+
+// lines under the day number taken by bars, per row; when they don't all fit in cells
+// `cell_height` high the last line is left for "+N more"
+fn shown_lanes(segments: &[Segment], cell_height: usize) -> [usize; 6] {
+    let mut lanes_in_row = [0usize; 6];
+    for seg in segments {
+        lanes_in_row[seg.row] = lanes_in_row[seg.row].max(seg.lane + 1);
+    }
+
+    let free = cell_height.saturating_sub(1);
+    for lanes in lanes_in_row.iter_mut() {
+        if *lanes > free {
+            *lanes = free.saturating_sub(1);
+        }
+    }
+    lanes_in_row
+}
+
+// bars going through cell `cell` which did not fit
+fn hidden_bars(segments: &[Segment], lanes_in_row: &[usize; 6], cell: usize) -> usize {
+    segments
+        .iter()
+        .filter(|s| s.row == cell / 7 && s.lane >= lanes_in_row[s.row])
+        .filter(|s| (s.first_col..=s.last_col).contains(&(cell % 7)))
+        .count()
+}
+
+// keeps the items fitting in `free` lines, the last line that fits tells how many did not
+fn fit_items(items: &mut Vec<Line>, free: usize, hidden: usize) {
+    if items.len() > free || hidden > 0 {
+        let shown = items.len().min(free.saturating_sub(1));
+        let more = items.len() - shown + hidden;
+        items.truncate(shown);
+        items.push(Line::styled(format!("+{more} more"), Style::new().dim()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::gcal::parse_ics;
+
+    // all-day events as (uid, first day, day after the last) in October 2025
+    fn all_day(events: &[(&str, u32, u32)]) -> Vec<CalendarEvent> {
+        let mut ics = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n");
+        for (uid, first, end) in events {
+            ics += &format!(
+                "BEGIN:VEVENT\r\nUID:{uid}\r\nSUMMARY:{uid}\r\n\
+                 DTSTART;VALUE=DATE:202510{first:02}\r\nDTEND;VALUE=DATE:202510{end:02}\r\n\
+                 END:VEVENT\r\n"
+            );
+        }
+        ics += "END:VCALENDAR\r\n";
+        parse_ics(ics.as_bytes()).unwrap().events
+    }
+
+    // October 2025 starts on a Wednesday, the third cell of the first row
+    fn segments(events: &[CalendarEvent]) -> Vec<Segment<'_>> {
+        let month_first = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        let month_last = NaiveDate::from_ymd_opt(2025, 10, 31).unwrap();
+        let events: Vec<&CalendarEvent> = events.iter().collect();
+
+        span_segments(&events, &Tz::UTC, month_first, month_last, |d| {
+            2 + (d - month_first).num_days() as usize
+        })
+    }
+
+    fn placed<'a>(segments: &[Segment<'a>]) -> Vec<(&'a str, usize, usize, usize, usize, bool)> {
+        segments
+            .iter()
+            .map(|s| {
+                (
+                    s.event.uid.as_str(),
+                    s.row,
+                    s.first_col,
+                    s.last_col,
+                    s.lane,
+                    s.continued,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bars_are_split_at_week_rows() {
+        // friday 3 to monday 6, then from the end of the month before to the 5th
+        let events = all_day(&[("trip", 3, 7)]);
+        assert_eq!(
+            placed(&segments(&events)),
+            [("trip", 0, 4, 6, 0, false), ("trip", 1, 0, 0, 0, true)]
+        );
+
+        let mut events = all_day(&[("holiday", 1, 6)]);
+        events[0].start = events[0].start.with_naive(
+            NaiveDate::from_ymd_opt(2025, 9, 29)
+                .unwrap()
+                .and_time(chrono::NaiveTime::MIN),
+        );
+        assert_eq!(placed(&segments(&events)), [("holiday", 0, 2, 6, 0, true)]);
+    }
+
+    #[test]
+    fn overlapping_bars_take_the_next_free_lane() {
+        // c fits in the lane of a once a is over, the longer of two starting together first
+        let events = all_day(&[("a", 6, 8), ("b", 6, 11), ("c", 9, 11), ("d", 8, 10)]);
+        assert_eq!(
+            placed(&segments(&events)),
+            [
+                ("b", 1, 0, 4, 0, false),
+                ("a", 1, 0, 1, 1, false),
+                ("d", 1, 2, 3, 1, false),
+                ("c", 1, 3, 4, 2, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn lanes_which_dont_fit_are_counted_as_more() {
+        // four bars through monday 6
+        let events = all_day(&[("a", 6, 7), ("b", 6, 7), ("c", 6, 8), ("d", 6, 8)]);
+        let segments = segments(&events);
+
+        // all of them fit under the day number of 5 lines high cells
+        assert_eq!(shown_lanes(&segments, 5)[1], 4);
+        assert_eq!(hidden_bars(&segments, &shown_lanes(&segments, 5), 7), 0);
+
+        // in 4 lines, the third is left for "+N more"
+        let lanes = shown_lanes(&segments, 4);
+        assert_eq!(lanes, [0, 2, 0, 0, 0, 0]);
+        assert_eq!(hidden_bars(&segments, &lanes, 7), 2);
+        // the longer ones are shown, nothing is hidden on tuesday
+        assert_eq!(hidden_bars(&segments, &lanes, 8), 0);
+        // other rows are not affected
+        assert_eq!(hidden_bars(&segments, &lanes, 14), 0);
+    }
+
+    #[test]
+    fn more_counts_hidden_items_and_bars() {
+        let line = |s: &str| Line::from(s.to_string());
+        let texts =
+            |items: &[Line]| -> Vec<String> { items.iter().map(|l| l.to_string()).collect() };
+
+        let mut items = vec![line("a"), line("b")];
+        fit_items(&mut items, 2, 0);
+        assert_eq!(texts(&items), ["a", "b"]);
+
+        let mut items = vec![line("a"), line("b"), line("c")];
+        fit_items(&mut items, 2, 0);
+        assert_eq!(texts(&items), ["a", "+2 more"]);
+
+        // hidden bars take the last line even when the items fit
+        let mut items = vec![line("a"), line("b")];
+        fit_items(&mut items, 2, 3);
+        assert_eq!(texts(&items), ["a", "+4 more"]);
+
+        let mut items = vec![];
+        fit_items(&mut items, 1, 2);
+        assert_eq!(texts(&items), ["+2 more"]);
     }
 }
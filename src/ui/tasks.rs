@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use chrono_tz::Tz;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use anyhow::Result;
+
+use crate::{
+    common::task::{Task, TaskStatus},
+    state::AppState,
+    ui::common::{
+        focusable::Focusable,
+        styles::title_style,
+        view::{FocusableView, View, ViewKind},
+    },
+};
+
+pub struct TaskListView {
+    state: AppState,
+    focused: bool,
+
+    // index of selected line
+    selected: usize,
+}
+
+impl TaskListView {
+    pub fn new(state: AppState) -> Self {
+        Self {
+            state,
+            focused: false,
+            selected: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        let state = self.state.state.borrow();
+        state
+            .calendar
+            .as_ref()
+            .map_or(0, |c| c.calendar().tasks.len())
+    }
+}
+
+impl View for TaskListView {
+    fn kind(&self) -> Option<ViewKind> {
+        Some(ViewKind::Tasks)
+    }
+
+    fn render(&self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let title = Line::from("TASKS".fg(Color::Green).style(title_style(self.focused)));
+
+        let block = Block::new().title(title.centered());
+        let mut inner_area = block.inner(area);
+
+        // little offset
+        inner_area.y += 1;
+        inner_area.height = inner_area.height.saturating_sub(1);
+
+        block.render(area, buf);
+
+        let state = self.state.state.borrow();
+        let Some(calendar) = &state.calendar else {
+            return;
+        };
+        let tz = &state.config.timezone;
+
+        // scrolled just enough for the selected line to be visible
+        let scroll = self
+            .selected
+            .saturating_sub(inner_area.height.saturating_sub(1) as usize);
+
+        let lines: Vec<Line> = task_tree(&calendar.calendar().tasks, tz)
+            .into_iter()
+            .enumerate()
+            .skip(scroll)
+            .map(|(i, (depth, task))| {
+                let line = task_line(task, depth, tz);
+
+                if self.focused && i == self.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        if lines.is_empty() {
+            Paragraph::new("No tasks".dim()).render(inner_area, buf);
+        } else {
+            Paragraph::new(lines).render(inner_area, buf);
+        }
+    }
+
+    fn handle_event(&mut self, e: &Event) -> Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            match key_ev.code {
+                KeyCode::Char('j') | KeyCode::Down => self.selected += 1,
+                KeyCode::Char('k') | KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) {
+        self.selected = self.selected.min(self.len().saturating_sub(1));
+
+        // tasks aren't events, details popup has nothing to show
+        self.state.state.borrow_mut().selected_event = None;
+    }
+
+    // no event details for Enter to open here
+    fn handles_key(&self, key: KeyCode) -> bool {
+        key == KeyCode::Enter
+    }
+}
+
+impl Focusable for TaskListView {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = !self.focused
+    }
+}
+
+impl FocusableView for TaskListView {
+    fn handle_event_if_focused(&mut self, e: &Event) -> Result<()> {
+        if self.focused {
+            self.handle_event(e)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// `[x] !! Summary  due Fri 24 Oct  40%`, subtasks indented under their parent
+fn task_line(task: &Task, depth: usize, tz: &Tz) -> Line<'static> {
+    let check = match task.status {
+        _ if task.is_done() => "[x] ",
+        TaskStatus::Cancelled => "[-] ",
+        _ => "[ ] ",
+    };
+
+    let mut spans = vec![Span::from("  ".repeat(depth)), Span::from(check)];
+
+    let priority = match task.priority {
+        1..=4 => Some(("!!! ", Color::Red)),
+        5 => Some(("!!  ", Color::Yellow)),
+        6..=9 => Some(("!   ", Color::Blue)),
+        _ => None,
+    };
+    if let Some((mark, color)) = priority {
+        spans.push(mark.fg(color));
+    }
+
+    let summary_style = if task.is_done() || task.status == TaskStatus::Cancelled {
+        Style::new().crossed_out().dim()
+    } else {
+        Style::new()
+    };
+    spans.push(Span::styled(
+        task.summary.clone().unwrap_or_default(),
+        summary_style,
+    ));
+
+    if let Some(due) = &task.due {
+        let format = if due.is_date() {
+            "%a %d %b"
+        } else {
+            "%a %d %b %H:%M"
+        };
+        let color = if task.is_overdue(Utc::now(), tz) {
+            Color::Red
+        } else {
+            Color::Blue
+        };
+        spans.push(format!("  due {}", due.in_zone(tz).format(format)).fg(color));
+    }
+
+    if let Some(percent) = task.percent_complete.filter(|p| *p > 0 && !task.is_done()) {
+        spans.push(format!("  {percent}%").dim());
+    }
+
+    Line::from(spans)
+}
+
+// tasks in display order with their depth: open ones first, then by due date
+// and priority, subtasks right after their parent
+fn task_tree<'a>(tasks: &'a [Task], tz: &Tz) -> Vec<(usize, &'a Task)> {
+    let uids: HashSet<&str> = tasks.iter().map(|t| t.uid.as_str()).collect();
+
+    let mut children: HashMap<&str, Vec<&Task>> = HashMap::new();
+    let mut roots = vec![];
+    for task in tasks {
+        match task.parent.as_deref() {
+            Some(parent) if parent != task.uid && uids.contains(parent) => {
+                children.entry(parent).or_default().push(task)
+            }
+            _ => roots.push(task),
+        }
+    }
+
+    let sort = |tasks: &mut Vec<&Task>| {
+        tasks.sort_by_key(|t| {
+            (
+                t.is_done() || t.status == TaskStatus::Cancelled,
+                t.due
+                    .as_ref()
+                    .map_or(chrono::DateTime::<Utc>::MAX_UTC, |d| d.utc(tz)),
+                // undefined priority goes last
+                if t.priority == 0 { 10 } else { t.priority },
+            )
+        })
+    };
+    sort(&mut roots);
+    children.values_mut().for_each(sort);
+
+    let mut tree = vec![];
+    let mut seen = HashSet::new();
+    let mut stack: Vec<(usize, &Task)> = roots.into_iter().rev().map(|t| (0, t)).collect();
+    while let Some((depth, task)) = stack.pop() {
+        if !seen.insert(std::ptr::from_ref(task)) {
+            continue;
+        }
+
+        tree.push((depth, task));
+        if let Some(children) = children.get(task.uid.as_str()) {
+            stack.extend(children.iter().rev().map(|t| (depth + 1, *t)));
+        }
+    }
+
+    // tasks in a parent cycle are never reached from a root
+    tree.extend(
+        tasks
+            .iter()
+            .filter(|t| !seen.contains(&std::ptr::from_ref(*t)))
+            .map(|t| (0, t)),
+    );
+
+    tree
+}
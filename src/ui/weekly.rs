@@ -0,0 +1,454 @@
+use std::cell::Cell;
+
+use chrono::{Datelike, Days, NaiveDate, NaiveTime, Utc, Weekday};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Widget},
+};
+
+use anyhow::Result;
+
+use crate::{
+    state::AppState,
+    ui::common::{
+        focusable::Focusable,
+        styles::title_style,
+        timeline::{
+            ALL_DAY_LINES, AXIS_WIDTH, DayEvents, TimeGrid, render_all_day, render_axis,
+            render_events, render_now_line,
+        },
+        utils::today,
+        view::{FocusableView, View, ViewKind},
+    },
+};
+
+// Seven days side by side on one hour axis, starting with the configured first weekday
+pub struct WeeklyView {
+    state: AppState,
+    focused: bool,
+
+    week_start: NaiveDate,
+
+    // cursor: day of the week, row in the timeline (`None` on the all-day row) and which
+    // of the events under it is selected, from the left
+    day: usize,
+    row: Option<usize>,
+    column: usize,
+
+    // date and time this view last selected, when the selection differs another view moved it
+    last_date: NaiveDate,
+    last_time: NaiveTime,
+
+    // first visible row, set while rendering
+    scroll: Cell<usize>,
+}
+
+impl WeeklyView {
+    pub fn new(state: AppState) -> Self {
+        let (date, time, row) = {
+            let state = state.state.borrow();
+            let time = state.selected_time;
+            let row = TimeGrid::new(&state.config).row_at(time);
+
+            (state.selected_date, time, row)
+        };
+
+        let mut view = Self {
+            state,
+            focused: false,
+            week_start: date,
+            day: 0,
+            row: Some(row),
+            column: 0,
+            last_date: date,
+            last_time: time,
+            scroll: Cell::new(0),
+        };
+        view.show_date(date);
+
+        view
+    }
+
+    // week of given day, with the cursor on it
+    fn show_date(&mut self, date: NaiveDate) {
+        let first_weekday = self.state.state.borrow().config.first_weekday;
+        self.day = days_since(date.weekday(), first_weekday);
+        self.week_start = date - Days::new(self.day as u64);
+        self.column = 0;
+    }
+
+    fn date(&self, day: usize) -> NaiveDate {
+        self.week_start + Days::new(day as u64)
+    }
+
+    fn grid(&self) -> TimeGrid {
+        TimeGrid::new(&self.state.state.borrow().config)
+    }
+
+    fn week_events(&self) -> Vec<DayEvents> {
+        let state = self.state.state.borrow();
+        (0..7)
+            .map(|d| DayEvents::load(&state, self.date(d)))
+            .collect()
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        let events = DayEvents::load(&self.state.state.borrow(), self.date(self.day));
+
+        match code {
+            KeyCode::Char('n') => self.week_start = self.week_start + Days::new(7),
+            KeyCode::Char('p') => self.week_start = self.week_start - Days::new(7),
+
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.day = self.day.saturating_sub(1);
+                self.column = 0;
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.day = (self.day + 1).min(6);
+                self.column = 0;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                let rows = self.grid().rows();
+                self.row = Some(self.row.map_or(0, |r| (r + 1).min(rows.saturating_sub(1))));
+                self.column = 0;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.row = match self.row {
+                    Some(0) if !events.all_day.is_empty() => None,
+                    Some(r) => Some(r.saturating_sub(1)),
+                    None => None,
+                };
+                self.column = 0;
+            }
+            // through events of the day, overlapping ones included
+            KeyCode::Char(c @ ('J' | 'K')) => {
+                if let Some((row, column)) = events.jump(self.row, c == 'J') {
+                    self.row = Some(row);
+                    self.column = column;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // day names with dates, today highlighted like in the monthly view
+    fn render_header(&self, columns: &[Rect], buf: &mut Buffer) {
+        let now = today(&self.state.state.borrow().config.timezone);
+
+        for (d, column) in columns.iter().enumerate() {
+            let date = self.date(d);
+            let style = if date == now {
+                Style::new().bg(Color::Blue).fg(Color::White)
+            } else if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                Style::new().fg(Color::Red)
+            } else {
+                Style::new().fg(Color::Blue)
+            };
+            let style = if self.focused && d == self.day {
+                style.bold().underlined()
+            } else {
+                style
+            };
+
+            buf.set_stringn(
+                column.x,
+                column.y,
+                date.format("%a %d").to_string(),
+                column.width as usize,
+                style,
+            );
+        }
+    }
+}
+
+// `WEEK 53, 28 Dec - 03 Jan 2027`, ISO week of the day most of the week falls into
+fn week_title(week_start: NaiveDate) -> String {
+    let week_end = week_start + Days::new(6);
+    format!(
+        "WEEK {}, {} - {}",
+        (week_start + Days::new(3)).iso_week().week(),
+        week_start.format("%d %b"),
+        week_end.format("%d %b %Y")
+    )
+}
+
+// days from `from` forward to `day`
+fn days_since(day: Weekday, from: Weekday) -> usize {
+    ((day.num_days_from_monday() + 7 - from.num_days_from_monday()) % 7) as usize
+}
+
+impl View for WeeklyView {
+    fn kind(&self) -> Option<ViewKind> {
+        Some(ViewKind::Weekly)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let grid = self.grid();
+        let week = self.week_events();
+
+        let title = Line::from(
+            week_title(self.week_start)
+                .fg(Color::Green)
+                .style(title_style(self.focused)),
+        );
+
+        let block = Block::new().title(title.alignment(Alignment::Center));
+        let mut inner_area = block.inner(area);
+
+        // little offset
+        inner_area.y += 1;
+        inner_area.height = inner_area.height.saturating_sub(1);
+
+        block.render(area, buf);
+
+        if inner_area.width <= AXIS_WIDTH || inner_area.height == 0 {
+            return;
+        }
+
+        // day columns, each but the last ends with a separator
+        let col_width = (inner_area.width - AXIS_WIDTH) / 7;
+        let column = |d: usize, y: u16, height: u16| {
+            let width = if d < 6 {
+                col_width.saturating_sub(1)
+            } else {
+                col_width
+            };
+            Rect::new(
+                inner_area.x + AXIS_WIDTH + d as u16 * col_width,
+                y,
+                width,
+                height,
+            )
+        };
+
+        let header: Vec<Rect> = (0..7).map(|d| column(d, inner_area.y, 1)).collect();
+        self.render_header(&header, buf);
+        inner_area.y += 1;
+        inner_area.height = inner_area.height.saturating_sub(1);
+
+        // all-day events of every day and a line under them
+        let all_day_height = week
+            .iter()
+            .map(|e| e.all_day.len().min(ALL_DAY_LINES))
+            .max()
+            .unwrap_or_default() as u16;
+        if all_day_height > 0 {
+            for (d, events) in week.iter().enumerate() {
+                let selected = match self.row {
+                    None if self.focused && d == self.day => events.at(None, self.column),
+                    _ => None,
+                };
+                render_all_day(
+                    &events.all_day,
+                    column(d, inner_area.y, all_day_height.min(inner_area.height)),
+                    selected,
+                    buf,
+                );
+            }
+
+            let line_y = inner_area.y + all_day_height;
+            if line_y < inner_area.y + inner_area.height {
+                buf.set_string(
+                    inner_area.x,
+                    line_y,
+                    "─".repeat(inner_area.width as usize),
+                    Style::new().dim(),
+                );
+            }
+
+            inner_area.y += all_day_height + 1;
+            inner_area.height = inner_area.height.saturating_sub(all_day_height + 1);
+        }
+
+        // scrolled just enough for the cursor to be visible
+        let height = inner_area.height as usize;
+        let cursor = self.row.unwrap_or_default();
+        let scroll = self
+            .scroll
+            .get()
+            .min(cursor)
+            .max((cursor + 1).saturating_sub(height))
+            .min(grid.rows().saturating_sub(height));
+        self.scroll.set(scroll);
+
+        let (tz, now) = {
+            let state = self.state.state.borrow();
+            let tz = state.config.timezone;
+            (tz, Utc::now())
+        };
+        let mut now_marker = None;
+
+        for (d, events) in week.iter().enumerate() {
+            let area = column(d, inner_area.y, inner_area.height);
+
+            if d < 6 {
+                for y in area.y..area.y + area.height {
+                    buf.set_string(area.x + area.width, y, "│", Style::new().dim());
+                }
+            }
+
+            if self.focused
+                && d == self.day
+                && let Some(y) = self.row.and_then(|r| r.checked_sub(scroll))
+                && y < height
+            {
+                let line = Rect::new(area.x, area.y + y as u16, area.width, 1);
+                buf.set_style(line, Style::new().bg(Color::DarkGray));
+            }
+
+            let now_row = grid.now_row(self.date(d), now, &tz);
+            if now_row.is_some() {
+                now_marker = now_row;
+            }
+            render_now_line(area, scroll, now_row, buf);
+
+            let selected = match self.row {
+                Some(_) if self.focused && d == self.day => events.at(self.row, self.column),
+                _ => None,
+            };
+            render_events(
+                &events.timed,
+                &events.boxes,
+                area,
+                scroll,
+                selected,
+                &tz,
+                buf,
+            );
+        }
+
+        let axis_area = Rect {
+            width: AXIS_WIDTH,
+            ..inner_area
+        };
+        let now_label = now.with_timezone(&tz).format("%H:%M").to_string();
+        render_axis(
+            &grid,
+            axis_area,
+            scroll,
+            now_marker.map(|r| (r, now_label)),
+            buf,
+        );
+    }
+
+    fn handle_event(&mut self, e: &Event) -> Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            self.handle_key(key_ev.code);
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) {
+        let grid = self.grid();
+        let (date, time) = {
+            let state = self.state.state.borrow();
+            (state.selected_date, state.selected_time)
+        };
+        if date != self.last_date {
+            self.show_date(date);
+        }
+        if time != self.last_time {
+            self.row = Some(grid.row_at(time));
+        }
+
+        let day = self.date(self.day);
+        let events = DayEvents::load(&self.state.state.borrow(), day);
+
+        // all-day row is gone when the day has no all-day events
+        if self.row.is_none() && events.all_day.is_empty() {
+            self.row = Some(0);
+        }
+        if let Some(row) = self.row {
+            self.row = Some(row.min(grid.rows().saturating_sub(1)));
+        }
+        self.column = self
+            .column
+            .min(events.under(self.row).len().saturating_sub(1));
+
+        let mut state = self.state.state.borrow_mut();
+        state.selected_event = events.event_at(self.row, self.column).cloned();
+        state.selected_date = day;
+        // the all-day row keeps the time selected before
+        if let Some(row) = self.row {
+            state.selected_time = grid.time_of(row);
+        }
+        self.last_date = day;
+        self.last_time = state.selected_time;
+    }
+}
+
+impl Focusable for WeeklyView {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = !self.focused
+    }
+}
+
+impl FocusableView for WeeklyView {
+    fn handle_event_if_focused(&mut self, e: &Event) -> Result<()> {
+        if self.focused {
+            self.handle_event(e)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn week_53_crosses_the_year() {
+        // 2026 starts on a thursday and has 53 weeks
+        assert_eq!(
+            week_title(date(2026, 12, 28)),
+            "WEEK 53, 28 Dec - 03 Jan 2027"
+        );
+        // weeks starting on sunday are numbered after their wednesday
+        assert_eq!(
+            week_title(date(2026, 12, 27)),
+            "WEEK 53, 27 Dec - 02 Jan 2027"
+        );
+        assert_eq!(week_title(date(2027, 1, 3)), "WEEK 1, 03 Jan - 09 Jan 2027");
+    }
+
+    #[test]
+    fn week_1_may_start_in_the_year_before() {
+        assert_eq!(
+            week_title(date(2025, 12, 29)),
+            "WEEK 1, 29 Dec - 04 Jan 2026"
+        );
+        // numbered after its middle day, tuesday 30 Dec is in week 1 of 2026
+        assert_eq!(
+            week_title(date(2025, 12, 27)),
+            "WEEK 1, 27 Dec - 02 Jan 2026"
+        );
+    }
+
+    #[test]
+    fn days_are_counted_from_the_first_weekday() {
+        assert_eq!(days_since(Weekday::Mon, Weekday::Mon), 0);
+        assert_eq!(days_since(Weekday::Sun, Weekday::Mon), 6);
+        assert_eq!(days_since(Weekday::Mon, Weekday::Sun), 1);
+        assert_eq!(days_since(Weekday::Fri, Weekday::Sat), 6);
+    }
+}
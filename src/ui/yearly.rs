@@ -0,0 +1,444 @@
+use std::{cell::Cell, collections::HashMap};
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc};
+use chrono_tz::Tz;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Widget},
+};
+
+use anyhow::Result;
+
+use crate::{
+    common::calendar::{CalendarEvent, EventStatus},
+    state::AppState,
+    ui::common::{
+        focusable::Focusable,
+        styles::title_style,
+        utils::{events_between, midnight, month_info, today},
+        view::{FocusableView, View, ViewKind},
+    },
+};
+
+// one mini month: `dd ` per day and a space between months
+const MONTH_WIDTH: u16 = 7 * 3 + 1;
+// month name, day names and six weeks
+const MONTH_HEIGHT: u16 = 8;
+
+// backgrounds for days with less than 2, 4, 6 and more booked hours
+const SHADES: [(u32, Color); 4] = [
+    (2 * 60, Color::Indexed(22)),
+    (4 * 60, Color::Indexed(28)),
+    (6 * 60, Color::Indexed(34)),
+    (u32::MAX, Color::Indexed(40)),
+];
+
+// Twelve months of a year, days shaded by booked hours
+pub struct YearlyView {
+    state: AppState,
+    focused: bool,
+
+    cursor: NaiveDate,
+    // day this view last selected, when the selection differs another view moved it
+    last_date: NaiveDate,
+
+    switch_request: Option<ViewKind>,
+
+    // first visible row of months, set while rendering
+    scroll: Cell<u16>,
+}
+
+impl YearlyView {
+    pub fn new(state: AppState) -> Self {
+        let selected = state.state.borrow().selected_date;
+
+        Self {
+            state,
+            focused: false,
+            cursor: selected,
+            last_date: selected,
+            switch_request: None,
+            scroll: Cell::new(0),
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        let cursor = match code {
+            KeyCode::Char('h') | KeyCode::Left => self.cursor.checked_sub_days(Days::new(1)),
+            KeyCode::Char('l') | KeyCode::Right => self.cursor.checked_add_days(Days::new(1)),
+            KeyCode::Char('k') | KeyCode::Up => self.cursor.checked_sub_days(Days::new(7)),
+            KeyCode::Char('j') | KeyCode::Down => self.cursor.checked_add_days(Days::new(7)),
+            KeyCode::Char('n') => self.cursor.checked_add_months(Months::new(12)),
+            KeyCode::Char('p') => self.cursor.checked_sub_months(Months::new(12)),
+
+            KeyCode::Enter => {
+                self.switch_request = Some(ViewKind::Monthly);
+                None
+            }
+            KeyCode::Char('d') => {
+                self.switch_request = Some(ViewKind::Daily);
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(cursor) = cursor {
+            self.cursor = cursor;
+        }
+    }
+
+    // minutes booked by timed events on each day of the year
+    fn booked_minutes(&self) -> HashMap<NaiveDate, u32> {
+        let state = self.state.state.borrow();
+
+        let Some(index) = &state.calendar else {
+            return HashMap::new();
+        };
+        let (Some(first), Some(last)) = (
+            NaiveDate::from_ymd_opt(self.cursor.year(), 1, 1),
+            NaiveDate::from_ymd_opt(self.cursor.year(), 12, 31),
+        ) else {
+            return HashMap::new();
+        };
+
+        let events = events_between(index, first, last, &state.config);
+        minutes_per_day(&events, &state.config.timezone)
+    }
+
+    fn render_month(
+        &self,
+        month: u32,
+        area: Rect,
+        booked: &HashMap<NaiveDate, u32>,
+        buf: &mut Buffer,
+    ) {
+        let year = self.cursor.year();
+        let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            return;
+        };
+        let (first_day, month_len) = month_info(year, month);
+        let now = today(&self.state.state.borrow().config.timezone);
+
+        let name_style = if month == self.cursor.month() {
+            Style::new().fg(Color::Green).bold()
+        } else {
+            Style::new().fg(Color::Green)
+        };
+        buf.set_stringn(
+            area.x,
+            area.y,
+            first.format("%B").to_string(),
+            area.width as usize,
+            name_style,
+        );
+
+        for (i, name) in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+            .iter()
+            .enumerate()
+        {
+            let color = if i >= 5 { Color::Red } else { Color::Blue };
+            buf.set_string(
+                area.x + i as u16 * 3,
+                area.y + 1,
+                name,
+                Style::new().fg(color),
+            );
+        }
+
+        for day_num in 0..month_len {
+            let cell = first_day + day_num;
+            let (x, y) = (
+                area.x + (cell % 7) as u16 * 3,
+                area.y + 2 + (cell / 7) as u16,
+            );
+            if y >= area.y + area.height {
+                break;
+            }
+
+            let date = first + Days::new(day_num as u64);
+            let mut style = match booked.get(&date) {
+                Some(&minutes) => {
+                    let (_, shade) = SHADES
+                        .iter()
+                        .find(|(limit, _)| minutes < *limit)
+                        .unwrap_or(&SHADES[SHADES.len() - 1]);
+                    Style::new().bg(*shade).fg(Color::White)
+                }
+                None => Style::new(),
+            };
+            if date == now {
+                style = style.underlined().bold();
+            }
+            if self.focused && date == self.cursor {
+                style = style.reversed();
+            }
+
+            buf.set_string(x, y, format!("{:>2}", day_num + 1), style);
+        }
+    }
+
+    // shade samples and hours booked on the cursor day
+    fn legend(&self, booked: &HashMap<NaiveDate, u32>) -> Line<'static> {
+        let mut spans = vec![Span::raw("booked ")];
+        for (label, (_, shade)) in ["<2h", "<4h", "<6h", "6h+"].iter().zip(SHADES) {
+            spans.push(Span::styled(format!(" {label} "), Style::new().bg(shade)));
+        }
+
+        let minutes = booked.get(&self.cursor).copied().unwrap_or_default();
+        spans.push(Span::raw(format!(
+            "   {}: {}h {:02}m",
+            self.cursor.format("%a %d %b"),
+            minutes / 60,
+            minutes % 60
+        )));
+
+        Line::from(spans)
+    }
+}
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+// minutes of each day taken by timed events which aren't cancelled, time taken by more
+// than one of them counts once
+fn minutes_per_day(events: &[CalendarEvent], tz: &Tz) -> HashMap<NaiveDate, u32> {
+    let mut intervals: HashMap<NaiveDate, Vec<Interval>> = HashMap::new();
+
+    for event in events {
+        if event.is_all_day() || event.status == EventStatus::Cancelled {
+            continue;
+        }
+
+        let (start, end) = (event.start_utc(tz), event.end_utc(tz));
+        let (first_day, last_day) = event.days(tz);
+        for day in first_day.iter_days().take_while(|d| *d <= last_day) {
+            let next = day.succ_opt().unwrap_or(day);
+            let from = start.max(midnight(day, tz));
+            let to = end.min(midnight(next, tz));
+
+            if to > from {
+                intervals.entry(day).or_default().push((from, to));
+            }
+        }
+    }
+
+    intervals
+        .into_iter()
+        .map(|(day, mut intervals)| {
+            intervals.sort();
+
+            let mut minutes = 0;
+            let mut covered: Option<Interval> = None;
+            for (from, to) in intervals {
+                match &mut covered {
+                    Some((_, end)) if from <= *end => *end = (*end).max(to),
+                    _ => {
+                        if let Some((start, end)) = covered {
+                            minutes += (end - start).num_minutes() as u32;
+                        }
+                        covered = Some((from, to));
+                    }
+                }
+            }
+            if let Some((start, end)) = covered {
+                minutes += (end - start).num_minutes() as u32;
+            }
+
+            (day, minutes)
+        })
+        .collect()
+}
+
+impl View for YearlyView {
+    fn kind(&self) -> Option<ViewKind> {
+        Some(ViewKind::Yearly)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(
+            format!("YEAR {}", self.cursor.year())
+                .fg(Color::Green)
+                .style(title_style(self.focused)),
+        );
+
+        let block = Block::new().title(title.alignment(Alignment::Center));
+        let mut inner_area = block.inner(area);
+
+        // little offset, last line is for the legend
+        inner_area.y += 1;
+        inner_area.height = inner_area.height.saturating_sub(2);
+
+        block.render(area, buf);
+
+        let booked = self.booked_minutes();
+
+        // as many months in a row as fit, up to four
+        let per_row = (inner_area.width / MONTH_WIDTH).clamp(1, 4);
+        let rows = 12u16.div_ceil(per_row);
+        let visible_rows = (inner_area.height / MONTH_HEIGHT).max(1);
+
+        // scrolled just enough for the cursor month to be visible
+        let cursor_row = (self.cursor.month0() as u16) / per_row;
+        let scroll = self
+            .scroll
+            .get()
+            .min(cursor_row)
+            .max((cursor_row + 1).saturating_sub(visible_rows))
+            .min(rows.saturating_sub(visible_rows));
+        self.scroll.set(scroll);
+
+        // months centered horizontally
+        let x = inner_area.x + (inner_area.width.saturating_sub(per_row * MONTH_WIDTH)) / 2;
+
+        for month0 in 0..12u16 {
+            let row = month0 / per_row;
+            if row < scroll || row >= scroll + visible_rows {
+                continue;
+            }
+
+            let month_area = Rect::new(
+                x + (month0 % per_row) * MONTH_WIDTH,
+                inner_area.y + (row - scroll) * MONTH_HEIGHT,
+                MONTH_WIDTH - 1,
+                MONTH_HEIGHT.min(inner_area.height),
+            )
+            .intersection(inner_area);
+            self.render_month(month0 as u32 + 1, month_area, &booked, buf);
+        }
+
+        let legend_y = inner_area.y + inner_area.height;
+        if legend_y < area.y + area.height {
+            self.legend(&booked)
+                .alignment(Alignment::Center)
+                .render(Rect::new(inner_area.x, legend_y, inner_area.width, 1), buf);
+        }
+    }
+
+    fn handle_event(&mut self, e: &Event) -> Result<()> {
+        if let Event::Key(key_ev) = e
+            && key_ev.kind == KeyEventKind::Press
+        {
+            self.handle_key(key_ev.code);
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) {
+        let mut state = self.state.state.borrow_mut();
+        if state.selected_date != self.last_date {
+            self.cursor = state.selected_date;
+        }
+
+        // no event selection in this view
+        state.selected_event = None;
+        state.selected_date = self.cursor;
+        self.last_date = self.cursor;
+    }
+
+    // Enter opens the day instead of event details
+    fn handles_key(&self, key: KeyCode) -> bool {
+        key == KeyCode::Enter
+    }
+
+    fn take_switch_request(&mut self) -> Option<ViewKind> {
+        self.switch_request.take()
+    }
+}
+
+impl Focusable for YearlyView {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = !self.focused
+    }
+}
+
+impl FocusableView for YearlyView {
+    fn handle_event_if_focused(&mut self, e: &Event) -> Result<()> {
+        if self.focused {
+            self.handle_event(e)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::gcal::parse_ics;
+
+    fn booked(events: &str, tz: Tz) -> Vec<(u32, u32)> {
+        let ics = format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{events}END:VCALENDAR\r\n");
+        let booked = minutes_per_day(&parse_ics(ics.as_bytes()).unwrap().events, &tz);
+
+        let mut days: Vec<_> = booked.into_iter().map(|(d, m)| (d.day(), m)).collect();
+        days.sort();
+        days
+    }
+
+    #[test]
+    fn minutes_are_split_at_midnight() {
+        let events = "BEGIN:VEVENT\r
+UID:night\r
+DTSTART:20251020T220000Z\r
+DTEND:20251022T023000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:lunch\r
+DTSTART:20251021T120000Z\r
+DTEND:20251021T130000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:holiday\r
+DTSTART;VALUE=DATE:20251023\r
+DTEND;VALUE=DATE:20251024\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:cancelled\r
+DTSTART:20251023T090000Z\r
+DTEND:20251023T170000Z\r
+STATUS:CANCELLED\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:standup\r
+DTSTART:20251022T020000Z\r
+DTEND:20251022T030000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:review\r
+DTSTART:20251022T100000Z\r
+DTEND:20251022T110000Z\r
+END:VEVENT\r
+";
+
+        // each event counts its part of a day, all-day and cancelled events are not
+        // counted; the lunch during the night and the standup half over it count once
+        assert_eq!(
+            booked(events, Tz::UTC),
+            [(20, 120), (21, 24 * 60), (22, 180 + 60)]
+        );
+    }
+
+    #[test]
+    fn days_are_taken_in_the_local_zone() {
+        // 25 hours on the day clocks go back in Paris
+        let events = "BEGIN:VEVENT\r
+UID:long\r
+DTSTART:20251025T220000Z\r
+DTEND:20251026T230000Z\r
+END:VEVENT\r
+";
+        assert_eq!(booked(events, chrono_tz::Europe::Paris), [(26, 25 * 60)]);
+    }
+}
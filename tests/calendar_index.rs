@@ -0,0 +1,244 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use taskrs::{
+    common::{
+        calendar::{CalendarEvent, CalendarQuery},
+        index::CalendarIndex,
+        recurrence::Until,
+    },
+    providers::gcal::parse_ics,
+};
+
+const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:old\r
+DTSTART:20240101T100000Z\r
+DTEND:20240101T110000Z\r
+SUMMARY:Old\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:weekly\r
+DTSTART:20250106T090000Z\r
+DTEND:20250106T100000Z\r
+RRULE:FREQ=WEEKLY\r
+SUMMARY:Weekly\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:semester\r
+DTSTART:20250901T000000Z\r
+DTEND:20251231T000000Z\r
+SUMMARY:Semester\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:daily\r
+DTSTART:20251001T080000Z\r
+DTEND:20251001T083000Z\r
+RRULE:FREQ=DAILY;UNTIL=20251010T235959Z\r
+SUMMARY:Daily\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:conference\r
+DTSTART:20251019T000000Z\r
+DTEND:20251022T000000Z\r
+SUMMARY:Conference\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:lunch\r
+DTSTART:20251020T120000Z\r
+DTEND:20251020T130000Z\r
+SUMMARY:Lunch\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:monthly\r
+DTSTART:20260301T090000Z\r
+DTEND:20260301T093000Z\r
+RRULE:FREQ=MONTHLY\r
+SUMMARY:Monthly\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+}
+
+fn index() -> CalendarIndex {
+    CalendarIndex::new(parse_ics(ICS.as_bytes()).unwrap(), Tz::UTC)
+}
+
+fn summaries(events: &[CalendarEvent]) -> Vec<&str> {
+    events.iter().filter_map(|e| e.summary.as_deref()).collect()
+}
+
+fn next(index: &CalendarIndex, after: DateTime<Utc>) -> Option<(String, DateTime<Utc>)> {
+    index
+        .next_event_after(after)
+        .map(|e| (e.summary.clone().unwrap(), e.start_utc(&Tz::UTC)))
+}
+
+#[test]
+fn range_includes_long_overlapping_events() {
+    let index = index();
+
+    let events = index.events_in_range(utc(2025, 10, 20, 0, 0), utc(2025, 10, 21, 0, 0));
+    assert_eq!(
+        summaries(&events),
+        ["Semester", "Conference", "Weekly", "Lunch"]
+    );
+
+    // only the long ones are still going on in the evening
+    let events = index.events_in_range(utc(2025, 10, 20, 18, 0), utc(2025, 10, 20, 19, 0));
+    assert_eq!(summaries(&events), ["Semester", "Conference"]);
+}
+
+#[test]
+fn range_edges_are_exclusive() {
+    let index = index();
+
+    let events = index.events_in_range(utc(2025, 10, 20, 10, 0), utc(2025, 10, 20, 12, 0));
+    assert_eq!(summaries(&events), ["Semester", "Conference"]);
+
+    let events = index.events_in_range(utc(2025, 10, 20, 13, 0), utc(2025, 10, 20, 14, 0));
+    assert_eq!(summaries(&events), ["Semester", "Conference"]);
+}
+
+#[test]
+fn range_far_from_other_events() {
+    let index = index();
+
+    assert!(
+        index
+            .events_in_range(utc(2023, 1, 1, 0, 0), utc(2023, 12, 31, 0, 0))
+            .is_empty()
+    );
+
+    let events = index.events_in_range(utc(2024, 1, 1, 0, 0), utc(2024, 1, 2, 0, 0));
+    assert_eq!(summaries(&events), ["Old"]);
+
+    // open series keep showing up years later
+    let events = index.events_in_range(utc(2030, 6, 1, 0, 0), utc(2030, 6, 8, 0, 0));
+    assert_eq!(summaries(&events), ["Monthly", "Weekly"]);
+    assert_eq!(events[0].start_utc(&Tz::UTC), utc(2030, 6, 1, 9, 0));
+    assert_eq!(events[1].start_utc(&Tz::UTC), utc(2030, 6, 3, 9, 0));
+}
+
+#[test]
+fn next_event_skips_started_ones() {
+    let index = index();
+
+    // the semester and the conference started before, the weekly one is done for the week
+    assert_eq!(
+        next(&index, utc(2025, 10, 20, 9, 30)),
+        Some(("Lunch".into(), utc(2025, 10, 20, 12, 0)))
+    );
+
+    assert_eq!(
+        next(&index, utc(2025, 10, 5, 8, 30)),
+        Some(("Daily".into(), utc(2025, 10, 6, 8, 0)))
+    );
+    assert_eq!(
+        next(&index, utc(2025, 10, 10, 8, 30)),
+        Some(("Weekly".into(), utc(2025, 10, 13, 9, 0)))
+    );
+}
+
+#[test]
+fn next_event_of_open_series() {
+    let index = index();
+
+    assert_eq!(
+        next(&index, utc(2023, 6, 1, 0, 0)),
+        Some(("Old".into(), utc(2024, 1, 1, 10, 0)))
+    );
+    assert_eq!(
+        next(&index, utc(2025, 12, 31, 0, 0)),
+        Some(("Weekly".into(), utc(2026, 1, 5, 9, 0)))
+    );
+    // 1 March 2026 is a Sunday, the monthly series comes before the weekly one
+    assert_eq!(
+        next(&index, utc(2026, 2, 28, 10, 0)),
+        Some(("Monthly".into(), utc(2026, 3, 1, 9, 0)))
+    );
+    assert_eq!(
+        next(&index, utc(2040, 1, 1, 0, 0)),
+        Some(("Monthly".into(), utc(2040, 1, 1, 9, 0)))
+    );
+}
+
+#[test]
+fn repeated_ranges_are_cached() {
+    let index = index();
+    let (from, to) = (utc(2025, 10, 20, 0, 0), utc(2025, 10, 27, 0, 0));
+
+    let first = index.events_in_range(from, to);
+    // other ranges in between don't change what the first one returns
+    for day in 1..=40 {
+        index.events_in_range(
+            utc(2025, 11, 1, 0, 0),
+            utc(2025, 11, 1, 0, 0) + Duration::days(day),
+        );
+    }
+    assert_eq!(index.events_in_range(from, to), first);
+    assert_eq!(
+        summaries(&first),
+        ["Semester", "Conference", "Weekly", "Lunch"]
+    );
+}
+
+#[test]
+fn series_until_the_end_of_time() {
+    let mut calendar = parse_ics(ICS.as_bytes()).unwrap();
+    let daily = calendar
+        .events
+        .iter_mut()
+        .find(|e| e.uid == "daily")
+        .unwrap();
+    // the last instance would end past the latest representable time
+    daily
+        .recurrence
+        .as_mut()
+        .unwrap()
+        .rule
+        .as_mut()
+        .unwrap()
+        .until = Some(Until::Utc(DateTime::<Utc>::MAX_UTC - Duration::minutes(10)));
+    let index = CalendarIndex::new(calendar, Tz::UTC);
+
+    let day = index.events_in_range(utc(2030, 5, 1, 0, 0), utc(2030, 5, 2, 0, 0));
+    assert!(summaries(&day).contains(&"Daily"));
+    assert_eq!(
+        next(&index, utc(2040, 1, 1, 0, 0)),
+        Some(("Daily".into(), utc(2040, 1, 1, 8, 0)))
+    );
+}
+
+#[test]
+fn far_ranges_dont_expand_from_the_start() {
+    // every minute but only 1 March at 9:00 matches; walking from 2000 the rule would
+    // give up after the first year of empty periods
+    let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:yearly\r
+DTSTART:20000301T090000Z\r
+DTEND:20000301T093000Z\r
+RRULE:FREQ=MINUTELY;BYMONTH=3;BYMONTHDAY=1;BYHOUR=9;BYMINUTE=0\r
+SUMMARY:Yearly\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let index = CalendarIndex::new(parse_ics(ics.as_bytes()).unwrap(), Tz::UTC);
+
+    let day = index.events_in_range(utc(2040, 3, 1, 9, 15), utc(2040, 3, 2, 0, 0));
+    assert_eq!(
+        day.iter()
+            .map(|e| e.start_utc(&Tz::UTC))
+            .collect::<Vec<_>>(),
+        [utc(2040, 3, 1, 9, 0)]
+    );
+    assert_eq!(
+        next(&index, utc(2040, 3, 1, 0, 0)),
+        Some(("Yearly".into(), utc(2040, 3, 1, 9, 0)))
+    );
+}
@@ -0,0 +1,635 @@
+use chrono::NaiveDateTime;
+use taskrs::{
+    common::{
+        calendar::{Calendar, EventStatus, PartStat, Role},
+        task::TaskStatus,
+        time::{EventDuration, EventTime, Zone},
+    },
+    providers::gcal::{parse_ics, write_event_ics, write_ics},
+};
+
+const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Google Inc//Google Calendar 70.9054//EN\r
+CALSCALE:GREGORIAN\r
+METHOD:PUBLISH\r
+X-WR-CALNAME:Work\\, mostly\r
+BEGIN:VTIMEZONE\r
+TZID:Europe/Kyiv\r
+X-LIC-LOCATION:Europe/Kyiv\r
+BEGIN:STANDARD\r
+DTSTART:19701025T040000\r
+TZOFFSETFROM:+0300\r
+TZOFFSETTO:+0200\r
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\r
+TZNAME:EET\r
+END:STANDARD\r
+END:VTIMEZONE\r
+BEGIN:VTIMEZONE\r
+TZID:FLE Standard Time\r
+BEGIN:STANDARD\r
+DTSTART:16010101T040000\r
+TZOFFSETFROM:+0300\r
+TZOFFSETTO:+0200\r
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\r
+TZNAME:EET\r
+END:STANDARD\r
+BEGIN:DAYLIGHT\r
+DTSTART:16010101T030000\r
+TZOFFSETFROM:+0200\r
+TZOFFSETTO:+0300\r
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3\r
+END:DAYLIGHT\r
+END:VTIMEZONE\r
+BEGIN:VEVENT\r
+UID:standup@example.com\r
+DTSTAMP:20251001T000000Z\r
+DTSTART;TZID=Europe/Kyiv:20251020T090000\r
+DTEND;TZID=Europe/Kyiv:20251020T091500\r
+RRULE:FREQ=WEEKLY;INTERVAL=2;UNTIL=20260101T000000Z;BYDAY=MO,WE;WKST=SU\r
+RDATE;TZID=Europe/Kyiv:20251025T100000\r
+EXDATE;TZID=Europe/Kyiv:20251022T090000\r
+SUMMARY:Standup; daily\\, short\r
+DESCRIPTION:Line one\\nLine two with a backslash \\\\ and a very long tail that\r
+  must be folded by the writer: Привіт, світ! こんにちは世界 🎉🎉🎉🎉🎉🎉🎉🎉\r
+LOCATION:Room 1\r
+STATUS:CONFIRMED\r
+ORGANIZER;CN=\"Doe, Jane\":mailto:jane@example.com\r
+ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=ACCEPTED;CN=Bob:mailto:bob@example.com\r
+ATTENDEE;CUTYPE=INDIVIDUAL;ROLE=OPT-PARTICIPANT;RSVP=TRUE;CN=me@example.com:\r
+ mailto:ME@example.com\r
+X-GOOGLE-CONFERENCE:https://meet.google.com/abc-defg-hij\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+TRIGGER;RELATED=START:-PT10M\r
+DESCRIPTION:This is an event reminder\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:standup@example.com\r
+DTSTAMP:20251001T000000Z\r
+RECURRENCE-ID;TZID=Europe/Kyiv:20251103T090000\r
+DTSTART;TZID=Europe/Kyiv:20251103T110000\r
+DTEND;TZID=Europe/Kyiv:20251103T113000\r
+SUMMARY:Standup (moved)\r
+STATUS:TENTATIVE\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:monthly@example.com\r
+DTSTAMP:20251001T000000Z\r
+DTSTART;TZID=FLE Standard Time:20251031T180000\r
+DTEND;TZID=FLE Standard Time:20251031T190000\r
+RRULE:FREQ=MONTHLY;COUNT=5;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1\r
+SUMMARY:Month end\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:trip@example.com\r
+DTSTAMP:20251001T000000Z\r
+DTSTART;VALUE=DATE:20251008\r
+DTEND;VALUE=DATE:20251013\r
+SUMMARY:Conference trip\r
+STATUS:CANCELLED\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:floating@example.com\r
+DTSTAMP:20251001T000000Z\r
+DTSTART:20251015T120000\r
+DTEND:20251015T130000Z\r
+SUMMARY:Lunch\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:duration@example.com\r
+DTSTAMP:20251001T000000Z\r
+DTSTART;TZID=Europe/Kyiv:20251025T090000\r
+DURATION:P1DT1H30M\r
+SUMMARY:Across DST\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:day@example.com\r
+DTSTAMP:20251001T000000Z\r
+DTSTART;VALUE=DATE:20251031\r
+SUMMARY:No end\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:instant@example.com\r
+DTSTAMP:20251001T000000Z\r
+DTSTART:20251031T100000Z\r
+SUMMARY:Deadline\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:todo@example.com\r
+DTSTAMP:20251001T000000Z\r
+SUMMARY:Write report\r
+DUE;VALUE=DATE:20251024\r
+PRIORITY:1\r
+STATUS:IN-PROCESS\r
+PERCENT-COMPLETE:40\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:todo-data@example.com\r
+DTSTAMP:20251001T000000Z\r
+SUMMARY:Collect data\r
+DUE;TZID=Europe/Kyiv:20251022T180000\r
+STATUS:COMPLETED\r
+COMPLETED:20251021T120000Z\r
+RELATED-TO:todo@example.com\r
+RELATED-TO;RELTYPE=SIBLING:other@example.com\r
+END:VTODO\r
+BEGIN:VJOURNAL\r
+UID:standup-notes@example.com\r
+DTSTAMP:20251020T100000Z\r
+DTSTART;VALUE=DATE:20251020\r
+DESCRIPTION:Did: parser\\nNext: writer\r
+DESCRIPTION:second one\r
+X-MOOD:fine\r
+END:VJOURNAL\r
+END:VCALENDAR\r
+";
+
+fn parse(s: &str) -> Calendar {
+    parse_ics(s.as_bytes()).expect("valid ics")
+}
+
+#[test]
+fn parse_write_parse_is_lossless() {
+    let parsed = parse(ICS);
+    let written = write_ics(&parsed).unwrap();
+    let reparsed = parse(&written);
+
+    assert_eq!(parsed, reparsed);
+
+    assert_eq!(parsed.name, "Work, mostly");
+    assert_eq!(parsed.events.len(), 7);
+    assert_eq!(parsed.events[0].overrides.len(), 1);
+    assert_eq!(
+        parsed.events[0].summary.as_deref(),
+        Some("Standup; daily, short")
+    );
+    assert!(
+        parsed.events[0]
+            .description
+            .as_deref()
+            .unwrap()
+            .starts_with("Line one\nLine two with a backslash \\ and")
+    );
+}
+
+#[test]
+fn unknown_data_is_kept() {
+    let parsed = parse(ICS);
+    // unfolded
+    let written = write_ics(&parsed).unwrap().replace("\r\n ", "");
+
+    for line in [
+        "METHOD:PUBLISH",
+        "X-LIC-LOCATION:Europe/Kyiv",
+        "X-GOOGLE-CONFERENCE:https://meet.google.com/abc-defg-hij",
+        "BEGIN:VTODO",
+        "DUE;VALUE=DATE:20251024",
+    ] {
+        assert!(written.contains(line), "missing {line:?} in:\n{written}");
+    }
+
+    assert_eq!(written.matches("BEGIN:VTIMEZONE").count(), 2);
+    assert_eq!(parsed.events[0].alarms.len(), 1);
+    assert!(parsed.components.is_empty());
+}
+
+#[test]
+fn writing_is_stable() {
+    let written = write_ics(&parse(ICS)).unwrap();
+    let rewritten = write_ics(&parse(&written)).unwrap();
+
+    // every component has a DTSTAMP, written back as read
+    assert_eq!(written, rewritten);
+}
+
+#[test]
+fn output_is_valid_icalendar() {
+    let written = write_ics(&parse(ICS)).unwrap();
+
+    assert!(written.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:"));
+    assert!(written.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(written.matches("DTSTAMP:").count(), 11);
+
+    for line in written.split("\r\n") {
+        assert!(line.len() <= 75, "line longer than 75 octets: {line:?}");
+        assert!(!line.contains('\n'));
+    }
+    assert!(written.contains("\r\n "), "long lines are folded");
+}
+
+#[test]
+fn text_escaping() {
+    let mut calendar = parse(ICS);
+    let text = "a,b;c\\d\ne\r\nf";
+    calendar.events[1].summary = Some(text.to_string());
+
+    let written = write_ics(&calendar).unwrap();
+    assert!(written.contains("SUMMARY:a\\,b\\;c\\\\d\\ne\\nf\r\n"));
+
+    let reparsed = parse(&written);
+    assert_eq!(
+        reparsed.events[1].summary.as_deref(),
+        Some("a,b;c\\d\ne\nf")
+    );
+}
+
+#[test]
+fn end_is_computed_without_dtend() {
+    let calendar = parse(ICS);
+    let event = |uid: &str| calendar.events.iter().find(|e| e.uid == uid).unwrap();
+
+    // days follow the wall clock, hours are exact
+    let across_dst = event("duration@example.com");
+    assert_eq!(across_dst.end.naive().to_string(), "2025-10-26 10:30:00");
+    assert_eq!(
+        across_dst.duration.map(|d| d.to_string()).as_deref(),
+        Some("P1DT1H30M")
+    );
+
+    let day = event("day@example.com");
+    assert_eq!(day.end.naive().to_string(), "2025-11-01 00:00:00");
+    assert!(day.end.is_date());
+
+    let instant = event("instant@example.com");
+    assert_eq!(instant.start, instant.end);
+
+    let written = write_ics(&calendar).unwrap();
+    assert!(written.contains("DURATION:P1DT1H30M\r\n"));
+}
+
+#[test]
+fn duration_values() {
+    for s in [
+        "P2W", "P1D", "PT1H", "PT1H30M", "-PT15M", "P1DT12H", "PT0S", "PT45S",
+    ] {
+        let d: EventDuration = s.parse().unwrap();
+        assert_eq!(d.to_string(), s);
+    }
+
+    let d: EventDuration = "P1W2D".parse().unwrap();
+    assert_eq!(d.days, 9);
+    assert!("PT".parse::<EventDuration>().is_err());
+    assert!("P1H".parse::<EventDuration>().is_err());
+    assert!("1D".parse::<EventDuration>().is_err());
+    assert!("P1DT".parse::<EventDuration>().is_err());
+
+    // too long for any date
+    assert!("P9999999999999W".parse::<EventDuration>().is_err());
+    assert!("PT99999999999999999H".parse::<EventDuration>().is_err());
+    assert!("P99999999999999999999D".parse::<EventDuration>().is_err());
+}
+
+#[test]
+fn out_of_range_duration_is_an_error() {
+    for duration in ["P9999999999999W", "P20000000W"] {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:long@example.com\r
+DTSTART:20251020T100000Z\r
+DURATION:{duration}\r
+END:VEVENT\r
+END:VCALENDAR\r
+"
+        );
+        assert!(parse_ics(ics.as_bytes()).is_err(), "{duration}");
+    }
+}
+
+#[test]
+fn attendees_and_organizer() {
+    let calendar = parse(ICS);
+    let event = &calendar.events[0];
+
+    let organizer = event.organizer.as_ref().unwrap();
+    assert_eq!(organizer.name.as_deref(), Some("Doe, Jane"));
+    assert!(organizer.is("jane@example.com"));
+
+    assert_eq!(event.attendees.len(), 2);
+    let bob = &event.attendees[0];
+    assert_eq!(bob.name.as_deref(), Some("Bob"));
+    assert_eq!(bob.role, Role::Required);
+    assert_eq!(bob.partstat, PartStat::Accepted);
+    assert!(!bob.rsvp);
+
+    let me = event.attendee("me@example.com").unwrap();
+    assert_eq!(me.role, Role::Optional);
+    assert_eq!(me.partstat, PartStat::NeedsAction);
+    assert!(me.rsvp);
+    assert_eq!(
+        me.params,
+        vec![("CUTYPE".to_string(), vec!["INDIVIDUAL".to_string()])]
+    );
+
+    assert!(event.is_accepted_by("bob@example.com"));
+    assert!(event.is_accepted_by("jane@example.com"));
+    assert!(!event.is_accepted_by("me@example.com"));
+    assert!(!event.is_accepted_by("someone@example.com"));
+    // no attendees, a personal event
+    assert!(calendar.events[1].is_accepted_by("someone@example.com"));
+
+    let written = write_ics(&calendar).unwrap().replace("\r\n ", "");
+    assert!(written.contains("ORGANIZER;CN=\"Doe, Jane\":mailto:jane@example.com\r\n"));
+    assert!(written.contains("ATTENDEE;CN=Bob;PARTSTAT=ACCEPTED:mailto:bob@example.com\r\n"));
+}
+
+#[test]
+fn tasks() {
+    let parsed = parse(ICS);
+    assert_eq!(parsed.tasks.len(), 2);
+
+    let report = &parsed.tasks[0];
+    assert_eq!(report.summary.as_deref(), Some("Write report"));
+    assert_eq!(report.priority, 1);
+    assert_eq!(report.status, TaskStatus::InProcess);
+    assert_eq!(report.percent_complete, Some(40));
+    assert!(!report.is_done());
+    assert!(matches!(report.due, Some(EventTime::Date(_))));
+
+    let data = &parsed.tasks[1];
+    assert_eq!(data.parent.as_deref(), Some("todo@example.com"));
+    assert!(data.is_done());
+    assert_eq!(
+        data.due_day(&chrono_tz::UTC),
+        chrono::NaiveDate::from_ymd_opt(2025, 10, 22)
+    );
+    // non-parent relations are kept as they are
+    assert_eq!(data.extra.len(), 1);
+
+    let written = write_ics(&parsed).unwrap();
+    for line in [
+        "PRIORITY:1",
+        "PERCENT-COMPLETE:40",
+        "STATUS:COMPLETED",
+        "COMPLETED:20251021T120000Z",
+        "RELATED-TO:todo@example.com",
+        "RELATED-TO;RELTYPE=SIBLING:other@example.com",
+        "DUE;TZID=Europe/Kyiv:20251022T180000",
+    ] {
+        assert!(written.contains(line), "missing {line:?} in:\n{written}");
+    }
+
+    // stamps are written back as read
+    for todo in written.split("BEGIN:VTODO").skip(1) {
+        assert!(todo.contains("DTSTAMP:20251001T000000Z\r\n"), "{todo}");
+    }
+}
+
+#[test]
+fn journal_entries() {
+    let parsed = parse(ICS);
+    assert_eq!(parsed.journals.len(), 1);
+
+    let entry = &parsed.journals[0];
+    assert_eq!(entry.description, "Did: parser\nNext: writer");
+    assert_eq!(
+        entry.day(&chrono_tz::UTC),
+        chrono::NaiveDate::from_ymd_opt(2025, 10, 20).unwrap()
+    );
+    // only the first description is the text, others are kept as they are
+    assert_eq!(entry.extra.len(), 2);
+
+    let written = write_ics(&parsed).unwrap();
+    for line in [
+        "DTSTART;VALUE=DATE:20251020",
+        "DESCRIPTION:Did: parser\\nNext: writer",
+        "DESCRIPTION:second one",
+        "X-MOOD:fine",
+        "DTSTAMP:20251020T100000Z",
+    ] {
+        assert!(written.contains(line), "missing {line:?} in:\n{written}");
+    }
+}
+
+#[test]
+fn single_event_source() {
+    let parsed = parse(ICS);
+    let written = write_event_ics(&parsed.events[0]);
+
+    assert!(written.starts_with("BEGIN:VEVENT\r\nUID:standup@example.com\r\n"));
+    assert!(written.ends_with("END:VEVENT\r\n"));
+    assert!(!written.contains("VCALENDAR"));
+    // the moved instance comes along
+    assert_eq!(written.matches("BEGIN:VEVENT").count(), 2);
+    assert!(written.contains("RECURRENCE-ID;TZID=Europe/Kyiv:20251103T090000"));
+}
+
+#[test]
+fn event_links() {
+    let mut event = parse(ICS).events[0].clone();
+    assert_eq!(event.urls(), ["https://meet.google.com/abc-defg-hij"]);
+
+    event.description = Some(
+        "Notes: <https://docs.example.com/notes>, see https://example.com/a?b=1.\n\
+         Again (https://docs.example.com/notes) and http:// alone"
+            .to_string(),
+    );
+    event.location = Some("https://maps.example.com/room".to_string());
+    assert_eq!(
+        event.urls(),
+        [
+            "https://maps.example.com/room",
+            "https://docs.example.com/notes",
+            "https://example.com/a?b=1",
+            "https://meet.google.com/abc-defg-hij",
+        ]
+    );
+}
+
+#[test]
+fn unsupported_rule_keeps_the_event() {
+    let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:easter@example.com\r
+DTSTART:20250420T090000Z\r
+DTEND:20250420T100000Z\r
+RRULE:FREQ=YEARLY;BYEASTER=0\r
+SUMMARY:Easter\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let parsed = parse(ics);
+    let event = &parsed.events[0];
+    assert!(event.recurrence.is_none());
+    assert_eq!(event.extra[0].name, "RRULE");
+
+    let written = write_ics(&parsed).unwrap();
+    assert!(written.contains("RRULE:FREQ=YEARLY;BYEASTER=0\r\n"));
+}
+
+#[test]
+fn iana_zones_get_a_vtimezone() {
+    let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:weekly@example.com\r
+DTSTART;TZID=America/New_York:20251020T090000\r
+DTEND;TZID=America/New_York:20251020T100000\r
+RRULE:FREQ=WEEKLY\r
+SUMMARY:Weekly\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let written = write_ics(&parse(ics)).unwrap();
+    assert!(written.contains("BEGIN:VTIMEZONE\r\nTZID:America/New_York\r\n"));
+
+    // clients without the IANA database get the same offsets from the block
+    let reparsed = parse(&written);
+    let vtz = reparsed
+        .timezones
+        .iter()
+        .find(|t| t.tzid == "America/New_York")
+        .unwrap();
+    let custom = Zone::Custom(vtz.clone());
+    let iana = Zone::Iana(chrono_tz::America::New_York);
+
+    for local in [
+        "2025-01-15 09:00",
+        "2025-10-20 09:00",
+        "2026-03-08 02:30",
+        "2026-11-01 01:30",
+        "2030-07-04 12:00",
+        "2035-12-31 23:00",
+    ] {
+        let local = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(custom.from_local(local), iana.from_local(local), "{local}");
+    }
+
+    let rewritten = write_ics(&reparsed).unwrap();
+    assert_eq!(rewritten.matches("BEGIN:VTIMEZONE").count(), 1);
+}
+
+#[test]
+fn parameter_quoting() {
+    let mut calendar = parse(ICS);
+    calendar.events[0].attendees[0].name = Some("Bob \"the builder\": on call".to_string());
+
+    let written = write_ics(&calendar).unwrap().replace("\r\n ", "");
+    assert!(written.contains("ATTENDEE;CN=\"Bob 'the builder': on call\";PARTSTAT=ACCEPTED:"));
+
+    let reparsed = parse(&written);
+    assert_eq!(
+        reparsed.events[0].attendees[0].name.as_deref(),
+        Some("Bob 'the builder': on call")
+    );
+}
+
+#[test]
+fn observance_properties_are_kept() {
+    let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VTIMEZONE\r
+TZID:Custom\r
+BEGIN:STANDARD\r
+DTSTART:19701025T040000\r
+TZOFFSETFROM:+0300\r
+TZOFFSETTO:+0200\r
+RDATE:19711031T040000\r
+RDATE;VALUE=PERIOD:19721029T040000/PT1H\r
+TZNAME:EET\r
+COMMENT:Winter time\r
+X-LIC-OBSERVANCE;X-SOURCE=tzdb:std\r
+END:STANDARD\r
+END:VTIMEZONE\r
+BEGIN:VEVENT\r
+UID:zoned@example.com\r
+DTSTART;TZID=Custom:20251020T090000\r
+SUMMARY:Zoned\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let parsed = parse(ics);
+    let observance = &parsed.timezones[0].observances[0];
+    assert_eq!(observance.rdates.len(), 1);
+    let names: Vec<&str> = observance.extra.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["RDATE", "COMMENT", "X-LIC-OBSERVANCE"]);
+
+    let written = write_ics(&parsed).unwrap();
+    for line in [
+        "RDATE:19711031T040000",
+        "RDATE;VALUE=PERIOD:19721029T040000/PT1H",
+        "COMMENT:Winter time",
+        "X-LIC-OBSERVANCE;X-SOURCE=tzdb:std",
+    ] {
+        assert!(written.contains(line), "missing {line:?} in:\n{written}");
+    }
+    assert_eq!(&parse(&written).timezones[0].observances[0], observance);
+}
+
+#[test]
+fn parent_relation_keeps_its_parameters() {
+    let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VTODO\r
+UID:child@example.com\r
+DTSTAMP:20251001T000000Z\r
+SUMMARY:Child\r
+RELATED-TO;RELTYPE=PARENT;X-ORDER=2:parent@example.com\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+    let parsed = parse(ics);
+    let task = &parsed.tasks[0];
+    assert_eq!(task.parent.as_deref(), Some("parent@example.com"));
+    assert!(task.extra.is_empty());
+
+    let written = write_ics(&parsed).unwrap();
+    assert!(written.contains("RELATED-TO;RELTYPE=PARENT;X-ORDER=2:parent@example.com\r\n"));
+    assert_eq!(parse(&written).tasks, parsed.tasks);
+}
+
+#[test]
+fn unknown_status_is_kept() {
+    let ics = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:postponed@example.com\r
+DTSTART:20251020T090000Z\r
+STATUS:X-POSTPONED\r
+SUMMARY:Postponed\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:waiting@example.com\r
+STATUS:X-WAITING\r
+SUMMARY:Waiting\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+    let parsed = parse(ics);
+    assert_eq!(parsed.events[0].status, EventStatus::Unknown);
+    assert_eq!(parsed.tasks[0].status, TaskStatus::Unknown);
+
+    let written = write_ics(&parsed).unwrap();
+    assert_eq!(written.matches("STATUS:").count(), 2);
+    assert!(written.contains("STATUS:X-POSTPONED\r\n"));
+    assert!(written.contains("STATUS:X-WAITING\r\n"));
+}
+
+#[test]
+fn calendar_header_is_kept() {
+    let ics = "BEGIN:VCALENDAR\r
+PRODID:-//Example//Lunar//EN\r
+VERSION:2.0\r
+CALSCALE:CHINESE\r
+METHOD:PUBLISH\r
+END:VCALENDAR\r
+";
+    let written = write_ics(&parse(ics)).unwrap();
+    assert!(written.starts_with(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Example//Lunar//EN\r\nCALSCALE:CHINESE\r\n"
+    ));
+    assert_eq!(written.matches("PRODID:").count(), 1);
+    assert_eq!(written.matches("CALSCALE:").count(), 1);
+    assert!(written.contains("METHOD:PUBLISH\r\n"));
+
+    // ours when the file has none
+    let written = write_ics(&parse(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nEND:VCALENDAR\r\n",
+    ))
+    .unwrap();
+    assert!(written.contains("PRODID:-//taskrs//taskrs//EN\r\nCALSCALE:GREGORIAN\r\n"));
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, TimeZone, Utc};
+use taskrs::{
+    common::{
+        calendar::{CalendarEvent, CalendarQuery, EventStatus},
+        index::CalendarIndex,
+    },
+    providers::gcal::{parse_ics, write_event_ics},
+};
+
+// weekly on mondays from 6 Oct; overrides come before their master: the 13th moves to
+// the 14th, the 20th is cancelled and the 27th moves out of October
+const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:sync@example.com\r
+RECURRENCE-ID:20251013T090000Z\r
+DTSTART:20251014T110000Z\r
+DTEND:20251014T113000Z\r
+SUMMARY:Sync (moved)\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:sync@example.com\r
+RECURRENCE-ID:20251020T090000Z\r
+DTSTART:20251020T090000Z\r
+DTEND:20251020T093000Z\r
+SUMMARY:Sync\r
+STATUS:CANCELLED\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:sync@example.com\r
+DTSTAMP:20250901T120000Z\r
+DTSTART:20251006T090000Z\r
+DTEND:20251006T093000Z\r
+RRULE:FREQ=WEEKLY;COUNT=4\r
+SUMMARY:Sync\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:sync@example.com\r
+RECURRENCE-ID:20251027T090000Z\r
+DTSTART:20251103T090000Z\r
+DTEND:20251103T093000Z\r
+SUMMARY:Sync (next month)\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+fn utc(m: u32, d: u32, h: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, m, d, h, 0, 0).unwrap()
+}
+
+fn master() -> CalendarEvent {
+    let mut calendar = parse_ics(ICS.as_bytes()).unwrap();
+    assert_eq!(
+        calendar.events.len(),
+        1,
+        "overrides are grouped with the master"
+    );
+    calendar.events.remove(0)
+}
+
+fn starts(events: &[CalendarEvent]) -> Vec<DateTime<Utc>> {
+    events
+        .iter()
+        .map(|e| e.start_utc(&chrono_tz::UTC))
+        .collect()
+}
+
+#[test]
+fn overrides_are_attached_to_a_later_master() {
+    assert_eq!(master().overrides.len(), 3);
+}
+
+#[test]
+fn moved_instance_leaves_its_slot() {
+    let october = master().occurrences_between(utc(10, 1, 0), utc(11, 1, 0), &chrono_tz::UTC);
+
+    assert_eq!(
+        starts(&october),
+        [utc(10, 6, 9), utc(10, 14, 11), utc(10, 20, 9)]
+    );
+    assert_eq!(october[1].summary.as_deref(), Some("Sync (moved)"));
+}
+
+#[test]
+fn cancelled_override_replaces_the_instance() {
+    let week = master().occurrences_between(utc(10, 20, 0), utc(10, 21, 0), &chrono_tz::UTC);
+
+    assert_eq!(week.len(), 1);
+    assert_eq!(week[0].status, EventStatus::Cancelled);
+}
+
+#[test]
+fn cancelled_series_has_no_next_instance() {
+    let mut master = master();
+    assert_eq!(
+        master
+            .next_occurrence_after(utc(10, 1, 0), &chrono_tz::UTC)
+            .map(|e| e.start_utc(&chrono_tz::UTC)),
+        Some(utc(10, 6, 9))
+    );
+
+    // only the overrides are left, they have a status of their own
+    master.status = EventStatus::Cancelled;
+    let next = master.next_occurrence_after(utc(10, 1, 0), &chrono_tz::UTC);
+    assert_eq!(
+        next.as_ref().map(|e| e.start_utc(&chrono_tz::UTC)),
+        Some(utc(10, 14, 11))
+    );
+    assert_eq!(next.unwrap().summary.as_deref(), Some("Sync (moved)"));
+
+    master.overrides.clear();
+    assert!(
+        master
+            .next_occurrence_after(utc(10, 1, 0), &chrono_tz::UTC)
+            .is_none()
+    );
+}
+
+#[test]
+fn overrides_move_in_and_out_of_the_range() {
+    // the 27th moved to November
+    let late_october = master().occurrences_between(utc(10, 27, 0), utc(11, 1, 0), &chrono_tz::UTC);
+    assert!(late_october.is_empty());
+
+    let november = master().occurrences_between(utc(11, 1, 0), utc(12, 1, 0), &chrono_tz::UTC);
+    assert_eq!(starts(&november), [utc(11, 3, 9)]);
+    assert_eq!(november[0].summary.as_deref(), Some("Sync (next month)"));
+
+    // the 13th moved a day later, out of a range holding only its original slot
+    let thirteenth = master().occurrences_between(utc(10, 13, 0), utc(10, 14, 0), &chrono_tz::UTC);
+    assert!(thirteenth.is_empty());
+}
+
+#[test]
+fn instances_are_shown_as_stored() {
+    let index = CalendarIndex::new(parse_ics(ICS.as_bytes()).unwrap(), chrono_tz::UTC);
+    let october = index.events_in_range(utc(10, 1, 0), utc(11, 1, 0));
+    let source = |e: &CalendarEvent| write_event_ics(index.stored(e).unwrap());
+
+    // a plain instance is written as its series, with the overrides
+    let series = source(&october[0]);
+    assert!(
+        series.starts_with("BEGIN:VEVENT\r\nUID:sync@example.com\r\nDTSTAMP:20250901T120000Z\r\n")
+    );
+    assert!(series.contains("DTSTART:20251006T090000Z\r\n"));
+    assert!(series.contains("RRULE:FREQ=WEEKLY;COUNT=4\r\n"));
+    assert!(!series.contains("RECURRENCE-ID:20251006T090000Z"));
+    assert_eq!(series.matches("BEGIN:VEVENT").count(), 4);
+
+    // a moved one as its override alone
+    let moved = source(&october[1]);
+    assert!(moved.contains("RECURRENCE-ID:20251013T090000Z\r\n"));
+    assert!(moved.contains("DTSTART:20251014T110000Z\r\n"));
+    assert!(!moved.contains("RRULE"));
+    assert_eq!(moved.matches("BEGIN:VEVENT").count(), 1);
+}
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use taskrs::{
+    common::{
+        alarm::{AlarmAction, AlarmTrigger},
+        index::CalendarIndex,
+        reminder::ReminderScheduler,
+    },
+    providers::gcal::parse_ics,
+};
+
+const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:standup@example.com\r
+DTSTART:20251020T090000Z\r
+DTEND:20251020T091500Z\r
+RRULE:FREQ=DAILY;COUNT=3\r
+SUMMARY:Standup\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+TRIGGER:-PT10M\r
+REPEAT:1\r
+DURATION:PT5M\r
+DESCRIPTION:Standup soon\r
+END:VALARM\r
+BEGIN:VALARM\r
+ACTION:EMAIL\r
+TRIGGER:-PT1H\r
+DESCRIPTION:Mail\r
+SUMMARY:Mail\r
+ATTENDEE:mailto:me@example.com\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:review@example.com\r
+DTSTART:20251020T140000Z\r
+DTEND:20251020T150000Z\r
+SUMMARY:Review\r
+BEGIN:VALARM\r
+ACTION:AUDIO\r
+TRIGGER;RELATED=END:PT0S\r
+END:VALARM\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+TRIGGER;VALUE=DATE-TIME:20251019T180000Z\r
+DESCRIPTION:Prepare review\r
+END:VALARM\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+fn utc(h: u32, m: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 10, 20, h, m, 0).unwrap()
+}
+
+fn index() -> CalendarIndex {
+    CalendarIndex::new(parse_ics(ICS.as_bytes()).unwrap(), Tz::UTC)
+}
+
+#[test]
+fn alarms_are_parsed() {
+    let calendar = parse_ics(ICS.as_bytes()).unwrap();
+
+    let standup = &calendar.events[0];
+    assert_eq!(standup.alarms.len(), 2);
+    assert!(standup.components.is_empty());
+    assert_eq!(standup.alarms[0].action, AlarmAction::Display);
+    assert_eq!(standup.alarms[0].repeat, 1);
+    assert_eq!(standup.alarms[1].action, AlarmAction::Email);
+    assert_eq!(standup.alarms[1].extra.len(), 2);
+
+    let review = &calendar.events[1];
+    assert!(matches!(
+        review.alarms[0].trigger,
+        AlarmTrigger::Relative { from_end: true, .. }
+    ));
+    assert_eq!(
+        review.alarms[1].fire_times(review, &Tz::UTC),
+        vec![Utc.with_ymd_and_hms(2025, 10, 19, 18, 0, 0).unwrap()]
+    );
+}
+
+#[test]
+fn reminders_fire_once_in_order() {
+    let index = index();
+    // alarms before the start are skipped
+    let mut scheduler = ReminderScheduler::new(utc(8, 0));
+
+    assert!(!scheduler.check(Some(&index), utc(8, 49)));
+    assert!(scheduler.check(Some(&index), utc(8, 50)));
+    assert_eq!(scheduler.pending(), 1);
+    assert_eq!(
+        scheduler.active().unwrap().text.as_deref(),
+        Some("Standup soon")
+    );
+
+    // repeat 5 minutes later, e-mail alarm is never shown
+    assert!(scheduler.check(Some(&index), utc(9, 0)));
+    assert_eq!(scheduler.pending(), 2);
+    assert!(!scheduler.check(Some(&index), utc(9, 0)));
+
+    scheduler.dismiss();
+    scheduler.dismiss();
+    assert!(scheduler.active().is_none());
+
+    // audio alarm at the end of the review
+    assert!(!scheduler.check(Some(&index), utc(14, 59)));
+    assert!(scheduler.check(Some(&index), utc(15, 0)));
+    assert_eq!(
+        scheduler.active().unwrap().event.summary.as_deref(),
+        Some("Review")
+    );
+
+    // next instance of the standup
+    scheduler.dismiss();
+    assert!(scheduler.check(Some(&index), utc(8, 50) + Duration::days(1)));
+    let reminder = scheduler.active().unwrap();
+    assert_eq!(
+        reminder.event.start_utc(&Tz::UTC),
+        utc(9, 0) + Duration::days(1)
+    );
+}
+
+#[test]
+fn snoozed_reminder_comes_back() {
+    let index = index();
+    let mut scheduler = ReminderScheduler::new(utc(8, 0));
+
+    assert!(scheduler.check(Some(&index), utc(8, 52)));
+    scheduler.snooze(utc(8, 52), Duration::minutes(5));
+    assert!(scheduler.active().is_none());
+
+    // the alarm's own repeat fires meanwhile
+    assert!(scheduler.check(Some(&index), utc(8, 56)));
+    scheduler.dismiss();
+
+    assert!(!scheduler.check(Some(&index), utc(8, 56) + Duration::seconds(30)));
+    assert!(scheduler.check(Some(&index), utc(8, 57)));
+    assert_eq!(scheduler.active().unwrap().at, utc(8, 57));
+}
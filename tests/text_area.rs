@@ -0,0 +1,143 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+};
+use taskrs::ui::{
+    common::view::{View, ViewWithCursorControl},
+    components::text_area::TextArea,
+};
+
+// 10 columns and 4 rows inside the border
+const AREA: Rect = Rect::new(0, 0, 12, 6);
+
+fn press(area: &mut TextArea, code: KeyCode) {
+    area.handle_event(&Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+        .unwrap();
+}
+
+fn type_str(area: &mut TextArea, s: &str) {
+    for c in s.chars() {
+        match c {
+            '\n' => press(area, KeyCode::Enter),
+            c => press(area, KeyCode::Char(c)),
+        }
+    }
+}
+
+// rows on screen and the cursor inside the border
+fn render(area: &TextArea) -> (Vec<String>, (u16, u16)) {
+    let mut buf = Buffer::empty(AREA);
+    let mut cursor = None;
+    area.render_with_cursor(AREA, &mut buf, &mut |x, y| cursor = Some((x, y)));
+
+    let rows = (1..AREA.height - 1)
+        .map(|y| {
+            (1..AREA.width - 1)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    let (x, y) = cursor.expect("focused text area sets cursor");
+
+    (rows, (x - 1, y - 1))
+}
+
+#[test]
+fn wraps_at_spaces() {
+    let mut area = TextArea::new("");
+    type_str(&mut area, "hello world foo");
+
+    let (rows, cursor) = render(&area);
+    assert_eq!(rows, ["hello", "world foo", "", ""]);
+    assert_eq!(cursor, (9, 1));
+}
+
+#[test]
+fn wide_characters_wrap_by_width() {
+    let mut area = TextArea::new("");
+    // two columns each, five of them fill a row
+    type_str(&mut area, "日本語の文字です");
+
+    let (rows, cursor) = render(&area);
+    assert_eq!(rows[0].replace(' ', ""), "日本語の文");
+    assert_eq!(rows[1].replace(' ', ""), "字です");
+    assert_eq!(cursor, (6, 1));
+
+    press(&mut area, KeyCode::Left);
+    assert_eq!(render(&area).1, (4, 1));
+
+    // a wide char doesn't fit in the last column and goes to the next row
+    let mut area = TextArea::new("");
+    type_str(&mut area, "abcdefghi日");
+    let (rows, cursor) = render(&area);
+    assert_eq!(rows[0], "abcdefghi");
+    assert_eq!(rows[1].replace(' ', ""), "日");
+    assert_eq!(cursor, (2, 1));
+}
+
+#[test]
+fn graphemes_are_edited_whole() {
+    let mut area = TextArea::new("");
+    // e + combining acute accent, family emoji joined by ZWJ
+    type_str(&mut area, "cafe\u{301}👨‍👩‍👧!");
+
+    press(&mut area, KeyCode::Left);
+    press(&mut area, KeyCode::Backspace);
+    assert_eq!(area.content(), "cafe\u{301}!");
+
+    press(&mut area, KeyCode::Left);
+    press(&mut area, KeyCode::Delete);
+    assert_eq!(area.content(), "caf!");
+
+    // typed before a lone combining mark, the char takes it
+    let mut area = TextArea::new("");
+    type_str(&mut area, "\u{301}bc");
+    press(&mut area, KeyCode::Home);
+    type_str(&mut area, "ez");
+    assert_eq!(area.content(), "e\u{301}zbc");
+}
+
+#[test]
+fn vertical_moves_keep_the_display_column() {
+    let mut area = TextArea::new("");
+    type_str(&mut area, "日本語\nabcdef");
+    render(&area);
+
+    // column 6 is right after the third wide char
+    press(&mut area, KeyCode::Up);
+    assert_eq!(render(&area).1, (6, 0));
+    type_str(&mut area, "!");
+    assert_eq!(area.content(), "日本語!\nabcdef");
+
+    // column 3 is in the middle of 本, the cursor stays before it
+    press(&mut area, KeyCode::Down);
+    press(&mut area, KeyCode::Home);
+    for _ in 0..3 {
+        press(&mut area, KeyCode::Right);
+    }
+    render(&area);
+    press(&mut area, KeyCode::Up);
+    assert_eq!(render(&area).1, (2, 0));
+    press(&mut area, KeyCode::Delete);
+    assert_eq!(area.content(), "日語!\nabcdef");
+}
+
+#[test]
+fn long_text_scrolls() {
+    let mut area = TextArea::new("");
+    type_str(&mut area, "1\n2\n3\n4\n5\n6");
+
+    let (rows, cursor) = render(&area);
+    assert_eq!(rows, ["3", "4", "5", "6"]);
+    assert_eq!(cursor, (1, 3));
+
+    for _ in 0..5 {
+        press(&mut area, KeyCode::Up);
+    }
+    let (rows, cursor) = render(&area);
+    assert_eq!(rows, ["1", "2", "3", "4"]);
+    assert_eq!(cursor, (1, 0));
+}
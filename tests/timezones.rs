@@ -0,0 +1,220 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use taskrs::{
+    common::{
+        calendar::Calendar,
+        time::{EventTime, Zone},
+    },
+    providers::gcal::parse_ics,
+};
+
+const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//test//EN\r
+BEGIN:VTIMEZONE\r
+TZID:Eastern\r
+BEGIN:STANDARD\r
+DTSTART:19701101T020000\r
+TZOFFSETFROM:-0400\r
+TZOFFSETTO:-0500\r
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r
+TZNAME:EST\r
+END:STANDARD\r
+BEGIN:DAYLIGHT\r
+DTSTART:19700308T020000\r
+TZOFFSETFROM:-0500\r
+TZOFFSETTO:-0400\r
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r
+TZNAME:EDT\r
+END:DAYLIGHT\r
+END:VTIMEZONE\r
+BEGIN:VEVENT\r
+UID:iana\r
+DTSTAMP:20250101T000000Z\r
+DTSTART;TZID=Europe/Kyiv:20250705T150000\r
+DTEND;TZID=Europe/Kyiv:20250705T160000\r
+SUMMARY:Iana\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:slash\r
+DTSTAMP:20250101T000000Z\r
+DTSTART;TZID=/Europe/Kyiv:20250705T150000\r
+DTEND;TZID=/Europe/Kyiv:20250705T160000\r
+SUMMARY:Slash\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:custom\r
+DTSTAMP:20250101T000000Z\r
+DTSTART;TZID=Eastern:20250115T090000\r
+DTEND;TZID=Eastern:20250715T090000\r
+SUMMARY:Custom\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:unknown\r
+DTSTAMP:20250101T000000Z\r
+DTSTART;TZID=Mars/Olympus:20250705T150000\r
+DTEND;TZID=Mars/Olympus:20250705T160000\r
+SUMMARY:Unknown\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:floating\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250705T150000\r
+DTEND:20250705T160000\r
+SUMMARY:Floating\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:utc\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250705T150000Z\r
+DTEND:20250705T160000Z\r
+SUMMARY:Utc\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+const KYIV: Tz = chrono_tz::Europe::Kyiv;
+
+fn calendar() -> Calendar {
+    parse_ics(ICS.as_bytes()).unwrap()
+}
+
+fn start_of(calendar: &Calendar, uid: &str) -> EventTime {
+    calendar
+        .events
+        .iter()
+        .find(|e| e.uid == uid)
+        .unwrap()
+        .start
+        .clone()
+}
+
+fn eastern() -> Zone {
+    match start_of(&calendar(), "custom") {
+        EventTime::Zoned(_, zone @ Zone::Custom(_)) => zone,
+        _ => panic!("Eastern is defined by the file"),
+    }
+}
+
+fn dt(s: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+}
+
+fn utc(s: &str) -> DateTime<Utc> {
+    dt(s).and_utc()
+}
+
+#[test]
+fn tzid_resolution() {
+    let calendar = calendar();
+
+    let EventTime::Zoned(_, Zone::Iana(tz)) = start_of(&calendar, "iana") else {
+        panic!("Europe/Kyiv is an IANA zone");
+    };
+    assert_eq!(tz, KYIV);
+
+    // some producers prefix IANA names with a slash
+    let EventTime::Zoned(_, Zone::Iana(tz)) = start_of(&calendar, "slash") else {
+        panic!("/Europe/Kyiv is an IANA zone");
+    };
+    assert_eq!(tz, KYIV);
+
+    let EventTime::Zoned(_, Zone::Custom(vtz)) = start_of(&calendar, "custom") else {
+        panic!("Eastern comes from the VTIMEZONE of the file");
+    };
+    assert_eq!(vtz.tzid, "Eastern");
+
+    assert_eq!(
+        start_of(&calendar, "unknown"),
+        EventTime::Floating(dt("2025-07-05 15:00"))
+    );
+}
+
+#[test]
+fn floating_utc_and_zoned_instants() {
+    let calendar = calendar();
+
+    // floating times follow the zone of the user
+    let floating = start_of(&calendar, "floating");
+    assert_eq!(floating.utc(&KYIV), utc("2025-07-05 12:00"));
+    assert_eq!(floating.utc(&Tz::UTC), utc("2025-07-05 15:00"));
+
+    let fixed = start_of(&calendar, "utc");
+    assert_eq!(fixed.utc(&KYIV), utc("2025-07-05 15:00"));
+    assert_eq!(fixed.utc(&Tz::UTC), utc("2025-07-05 15:00"));
+
+    let zoned = start_of(&calendar, "iana");
+    assert_eq!(zoned.utc(&KYIV), utc("2025-07-05 12:00"));
+    assert_eq!(zoned.utc(&Tz::UTC), utc("2025-07-05 12:00"));
+    assert_eq!(zoned.in_zone(&KYIV).naive_local(), dt("2025-07-05 15:00"));
+
+    // winter and summer offsets of the custom zone
+    let event = calendar.events.iter().find(|e| e.uid == "custom").unwrap();
+    assert_eq!(event.start.utc(&KYIV), utc("2025-01-15 14:00"));
+    assert_eq!(event.end.utc(&KYIV), utc("2025-07-15 13:00"));
+}
+
+#[test]
+fn custom_zone_gap_and_overlap() {
+    let zone = eastern();
+
+    // 02:30 does not exist on 9 March, shifted forward to 03:30 EDT
+    assert_eq!(
+        zone.from_local(dt("2025-03-09 02:30")),
+        utc("2025-03-09 07:30")
+    );
+    assert_eq!(
+        zone.from_local(dt("2025-03-09 01:59")),
+        utc("2025-03-09 06:59")
+    );
+    assert_eq!(
+        zone.from_local(dt("2025-03-09 03:00")),
+        utc("2025-03-09 07:00")
+    );
+
+    // 01:30 happens twice on 2 November, the EDT one comes first
+    assert_eq!(
+        zone.from_local(dt("2025-11-02 01:30")),
+        utc("2025-11-02 05:30")
+    );
+    assert_eq!(
+        zone.from_local(dt("2025-11-02 02:00")),
+        utc("2025-11-02 07:00")
+    );
+
+    assert_eq!(
+        zone.to_local(utc("2025-03-09 06:59")),
+        dt("2025-03-09 01:59")
+    );
+    assert_eq!(
+        zone.to_local(utc("2025-03-09 07:00")),
+        dt("2025-03-09 03:00")
+    );
+    assert_eq!(
+        zone.to_local(utc("2025-11-02 05:30")),
+        dt("2025-11-02 01:30")
+    );
+    assert_eq!(
+        zone.to_local(utc("2025-11-02 06:30")),
+        dt("2025-11-02 01:30")
+    );
+}
+
+#[test]
+fn iana_zone_gap_and_overlap() {
+    let zone = Zone::Iana(KYIV);
+
+    // same rules as custom zones: gaps shift forward, overlaps take the earliest
+    assert_eq!(
+        zone.from_local(dt("2025-03-30 03:30")),
+        utc("2025-03-30 01:30")
+    );
+    assert_eq!(
+        zone.from_local(dt("2025-10-26 03:30")),
+        utc("2025-10-26 00:30")
+    );
+    assert_eq!(
+        zone.to_local(utc("2025-10-26 01:30")),
+        dt("2025-10-26 03:30")
+    );
+}
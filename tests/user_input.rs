@@ -0,0 +1,197 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+};
+use taskrs::ui::{
+    common::view::{Resettable, View, ViewWithCursorControl},
+    components::input::UserInput,
+};
+
+fn press(input: &mut UserInput, code: KeyCode) {
+    press_with(input, code, KeyModifiers::NONE);
+}
+
+fn press_with(input: &mut UserInput, code: KeyCode, modifiers: KeyModifiers) {
+    input
+        .handle_event(&Event::Key(KeyEvent::new(code, modifiers)))
+        .unwrap();
+}
+
+fn ctrl(input: &mut UserInput, c: char) {
+    press_with(input, KeyCode::Char(c), KeyModifiers::CONTROL);
+}
+
+fn type_str(input: &mut UserInput, s: &str) {
+    for c in s.chars() {
+        press(input, KeyCode::Char(c));
+    }
+}
+
+// cursor column inside the box border
+fn cursor_x(input: &UserInput) -> u16 {
+    let area = Rect::new(0, 0, 40, 3);
+    let mut buf = Buffer::empty(area);
+    let mut cursor = None;
+    input.render_with_cursor(area, &mut buf, &mut |x, y| cursor = Some((x, y)));
+
+    let (x, y) = cursor.expect("focused input sets cursor");
+    assert_eq!(y, 1);
+    x - 1
+}
+
+#[test]
+fn multibyte_editing() {
+    let mut input = UserInput::new("");
+    type_str(&mut input, "/home/олена/календар.ics");
+    assert_eq!(cursor_x(&input), 24);
+
+    for _ in 0..4 {
+        press(&mut input, KeyCode::Left);
+    }
+    press(&mut input, KeyCode::Backspace);
+    type_str(&mut input, "ь");
+    press(&mut input, KeyCode::Delete);
+    assert_eq!(input.content(), "/home/олена/календаьics");
+
+    press(&mut input, KeyCode::Home);
+    press(&mut input, KeyCode::Left);
+    press(&mut input, KeyCode::Backspace);
+    press(&mut input, KeyCode::End);
+    press(&mut input, KeyCode::Right);
+    type_str(&mut input, "é");
+    assert_eq!(input.content(), "/home/олена/календаьicsé");
+}
+
+#[test]
+fn graphemes_are_edited_whole() {
+    let mut input = UserInput::new("");
+    // e + combining acute accent, family emoji joined by ZWJ
+    type_str(&mut input, "cafe\u{301}👨‍👩‍👧!");
+
+    press(&mut input, KeyCode::Left);
+    press(&mut input, KeyCode::Backspace);
+    assert_eq!(input.content(), "cafe\u{301}!");
+
+    press(&mut input, KeyCode::Backspace);
+    assert_eq!(input.content(), "caf!");
+
+    // typed before a lone combining mark, the char takes it
+    let mut input = UserInput::new("");
+    type_str(&mut input, "\u{301}bc");
+    press(&mut input, KeyCode::Home);
+    type_str(&mut input, "ez");
+    assert_eq!(input.content(), "e\u{301}zbc");
+}
+
+#[test]
+fn wide_characters_move_cursor_two_cells() {
+    let mut input = UserInput::new("");
+    type_str(&mut input, "日本語.ics");
+    assert_eq!(cursor_x(&input), 10);
+
+    for _ in 0..4 {
+        press(&mut input, KeyCode::Left);
+    }
+    assert_eq!(cursor_x(&input), 6);
+
+    press(&mut input, KeyCode::Backspace);
+    assert_eq!(input.content(), "日本.ics");
+    assert_eq!(cursor_x(&input), 4);
+}
+
+#[test]
+fn readline_keys() {
+    let mut input = UserInput::new("");
+    type_str(&mut input, "~/cal/work.ics extra");
+
+    ctrl(&mut input, 'w');
+    assert_eq!(input.content(), "~/cal/work.ics ");
+
+    press_with(&mut input, KeyCode::Backspace, KeyModifiers::ALT);
+    assert_eq!(input.content(), "~/cal/work.");
+
+    press_with(&mut input, KeyCode::Char('b'), KeyModifiers::ALT);
+    assert_eq!(cursor_x(&input), 6);
+    ctrl(&mut input, 'k');
+    assert_eq!(input.content(), "~/cal/");
+
+    ctrl(&mut input, 'a');
+    ctrl(&mut input, 'y');
+    assert_eq!(input.content(), "work.~/cal/");
+
+    ctrl(&mut input, 'e');
+    ctrl(&mut input, 'u');
+    assert_eq!(input.content(), "");
+    ctrl(&mut input, 'y');
+    assert_eq!(input.content(), "work.~/cal/");
+
+    input
+        .handle_event(&Event::Paste("a\nb".to_string()))
+        .unwrap();
+    assert_eq!(input.content(), "work.~/cal/a b");
+}
+
+#[test]
+fn long_input_scrolls() {
+    let mut input = UserInput::new("");
+    let text = "x".repeat(60);
+    type_str(&mut input, &text);
+    // 38 columns inside the border, cursor stays in the last one
+    assert_eq!(cursor_x(&input), 37);
+
+    press(&mut input, KeyCode::Home);
+    assert_eq!(cursor_x(&input), 0);
+}
+
+#[test]
+fn wide_character_cut_by_scroll() {
+    let mut input = UserInput::new("");
+    // 41 columns, scrolled by 4: the second 日 starts in the third column and is cut
+    type_str(&mut input, &format!("x{}", "日".repeat(20)));
+
+    let area = Rect::new(0, 0, 40, 3);
+    let mut buf = Buffer::empty(area);
+    input.render_with_cursor(area, &mut buf, &mut |_, _| {});
+
+    // its visible half is blank, the others stay under their columns
+    assert_eq!(buf[(1, 1)].symbol(), " ");
+    assert_eq!(buf[(2, 1)].symbol(), "日");
+    assert_eq!(buf[(36, 1)].symbol(), "日");
+    assert_eq!(cursor_x(&input), 37);
+}
+
+#[test]
+fn no_room_inside_the_border() {
+    let mut input = UserInput::new("");
+    type_str(&mut input, "~/cal/work.ics");
+
+    // only the border fits, no cursor is set
+    for area in [Rect::new(0, 0, 2, 3), Rect::new(0, 0, 40, 2)] {
+        let mut buf = Buffer::empty(area);
+        let mut cursor = None;
+        input.render_with_cursor(area, &mut buf, &mut |x, y| cursor = Some((x, y)));
+        assert_eq!(cursor, None);
+    }
+}
+
+#[test]
+fn path_completion() {
+    let dir = std::env::temp_dir().join(format!("taskrs-completion-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("calendars")).unwrap();
+    std::fs::write(dir.join("work.ics"), "").unwrap();
+    std::fs::write(dir.join("work-old.ics"), "").unwrap();
+
+    let mut input = UserInput::new("").with_path_completion();
+    type_str(&mut input, &format!("{}/ca", dir.display()));
+    press(&mut input, KeyCode::Tab);
+    assert_eq!(input.content(), format!("{}/calendars/", dir.display()));
+
+    input.reset().unwrap();
+    type_str(&mut input, &format!("{}/w", dir.display()));
+    press(&mut input, KeyCode::Tab);
+    assert_eq!(input.content(), format!("{}/work", dir.display()));
+    assert_eq!(input.completions(), ["work-old.ics", "work.ics"]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}